@@ -0,0 +1,116 @@
+//! With the `embed-inputs` feature, generates the list of inputs and examples in the data
+//! directory that are compiled into the binaries, see `src/embedded.rs`. Without it the lists
+//! are empty.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The entries of the directory at `path` whose names parse as `T`, sorted by name
+fn numbered_entries<T: std::str::FromStr + Ord>(path: &Path) -> Vec<(T, PathBuf)> {
+    let mut entries: Vec<_> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let number = path.file_stem()?.to_str()?.parse::<T>().ok()?;
+            Some((number, path))
+        })
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Whether `input` looks like a puzzle input, the same checks as `input::validate_input`. A
+/// broken input would otherwise be served ahead of the cache and adventofcode.com.
+fn is_valid_input(input: &str) -> bool {
+    let start = input.trim_start().to_ascii_lowercase();
+    !(input.trim().is_empty()
+        || input.contains("Please don't repeatedly request this endpoint before it unlocks")
+        || input.contains("Puzzle inputs differ by user")
+        || start.starts_with("<!doctype html")
+        || start.starts_with("<html"))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut inputs = String::new();
+    let mut examples = String::new();
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        println!("cargo:rerun-if-env-changed=AOC_DATA_DIR");
+        let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+        let data_dir = manifest_dir.join(env::var("AOC_DATA_DIR").unwrap_or("data".to_string()));
+
+        // Watching a directory watches everything below it, and the runtimes and the time of
+        // the last request in the data directory change on every run. So the data and year
+        // directories are only watched until the directories the files are embedded from exist.
+        let years = numbered_entries::<u16>(&data_dir);
+        if years.is_empty() {
+            println!("cargo:rerun-if-changed={}", data_dir.display());
+        }
+        for (year, year_dir) in years {
+            let dirs = [year_dir.join("inputs"), year_dir.join("examples")];
+            if dirs.iter().all(|dir| dir.exists()) {
+                for dir in &dirs {
+                    println!("cargo:rerun-if-changed={}", dir.display());
+                }
+            } else {
+                println!("cargo:rerun-if-changed={}", year_dir.display());
+            }
+            // Only the inputs of the default profile, the others are in subdirectories
+            for (day, path) in numbered_entries::<u8>(&year_dir.join("inputs")) {
+                if path.extension().is_none_or(|extension| extension != "txt") {
+                    continue;
+                }
+                if !fs::read_to_string(&path).is_ok_and(|input| is_valid_input(&input)) {
+                    println!(
+                        "cargo:warning=Not embedding {}, it is not a valid input",
+                        path.display()
+                    );
+                    continue;
+                }
+                writeln!(inputs, "    ({}, {}, include_str!({:?})),", year, day, path).unwrap();
+            }
+            for (day, day_dir) in numbered_entries::<u8>(&year_dir.join("examples")) {
+                let mut paths: Vec<_> = fs::read_dir(&day_dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+                    .filter(|path| fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0))
+                    .collect();
+                paths.sort();
+                for path in paths {
+                    let name = path.file_stem().unwrap().to_string_lossy();
+                    let answers =
+                        fs::read_to_string(path.with_extension("answers.toml")).unwrap_or_default();
+                    writeln!(
+                        examples,
+                        "    ({}, {}, {:?}, include_str!({:?}), {:?}),",
+                        year, day, name, path, answers
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    let code = format!(
+        "/// Inputs of the default profile as year, day and input\n\
+         pub const INPUTS: &[(u16, u8, &str)] = &[\n{}];\n\n\
+         /// Examples as year, day, name, input and the content of the expected answers file\n\
+         pub const EXAMPLES: &[(u16, u8, &str, &str, &str)] = &[\n{}];\n",
+        inputs, examples
+    );
+    let path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("embedded.rs");
+    // Rewriting the file would recompile the crate even if nothing changed
+    if fs::read_to_string(&path).ok().as_deref() != Some(code.as_str()) {
+        fs::write(path, code).unwrap();
+    }
+}
@@ -1,16 +1,11 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
-use aoc24::input::get_input;
-
-pub fn part_one(a: &mut [usize], b: &mut Vec<usize>) -> usize {
-    a.sort();
-    b.sort();
-
-    a.iter_mut()
-        .zip(b)
-        .map(|(left, right)| left.abs_diff(*right))
-        .sum()
-}
+use aoc24::{
+    parse::{lines, parse_all, unsigned, ParseError},
+    runner::Runner,
+    Solution,
+};
+use nom::{character::complete::space1, sequence::separated_pair};
 
 fn get_hashmap(a: &[usize]) -> HashMap<usize, usize> {
     a.iter().fold(HashMap::new(), |mut map, el| {
@@ -23,54 +18,58 @@ fn get_hashmap(a: &[usize]) -> HashMap<usize, usize> {
     })
 }
 
-pub fn part_two(a: &[usize], b: &[usize]) -> usize {
-    let hashmap_b = get_hashmap(b);
+struct Day1;
 
-    a.iter().fold(0, |acc, el| {
-        if let Some(count) = hashmap_b.get(el) {
-            acc + el * count
-        } else {
-            acc
-        }
-    })
-}
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    /// The left and the right list
+    type Parsed = (Vec<usize>, Vec<usize>);
 
-fn parse_input(input: &str) -> (Vec<usize>, Vec<usize>) {
-    let lines = input.lines();
-    lines
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let a: usize = parts.next().unwrap().parse().unwrap();
-            let b: usize = parts.next().unwrap().parse().unwrap();
-            (a, b)
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let pairs: Vec<(usize, usize)> =
+            parse_all(lines(separated_pair(unsigned, space1, unsigned)), input)?;
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part_one((a, b): &mut Self::Parsed) -> impl Display {
+        a.sort();
+        b.sort();
+
+        a.iter()
+            .zip(b.iter())
+            .map(|(left, right)| left.abs_diff(*right))
+            .sum::<usize>()
+    }
+
+    fn part_two((a, b): &mut Self::Parsed) -> impl Display {
+        let hashmap_b = get_hashmap(b);
+
+        a.iter().fold(0, |acc, el| {
+            if let Some(count) = hashmap_b.get(el) {
+                acc + el * count
+            } else {
+                acc
+            }
         })
-        .unzip()
+    }
 }
+
 fn main() {
-    let input = get_input(1);
-    let (mut a, mut b) = parse_input(&input);
-    println!("Part one: {}", part_one(&mut a, &mut b));
-    println!("Part two: {}", part_two(&a, &b));
+    Runner::solve::<Day1>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(1);
-        let (mut a, mut b) = parse_input(&input);
-        let distances = part_one(&mut a, &mut b);
-        assert_eq!(distances, 11);
+        check_solution::<Day1>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(1);
-        let (a, b) = parse_input(&input);
-        let result = part_two(&a, &b);
-        assert_eq!(result, 31);
+        check_solution::<Day1>(Part::Two);
     }
 }
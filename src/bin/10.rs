@@ -1,4 +1,6 @@
-use aoc24::input::get_input;
+use std::fmt::Display;
+
+use aoc24::{parse::ParseError, runner::Runner, Solution};
 use itertools::Itertools;
 
 #[derive(Debug)]
@@ -16,24 +18,6 @@ fn get_zero_positions(map: &Map) -> impl Iterator<Item = Position> + '_ {
     })
 }
 
-fn part_one(map: &Map) -> usize {
-    get_zero_positions(map)
-        .map(|zero_position| {
-            get_paths(map, zero_position)
-                .iter()
-                .map(|p| p.last().unwrap())
-                .unique()
-                .count()
-        })
-        .sum()
-}
-
-fn part_two(map: &Map) -> usize {
-    get_zero_positions(map)
-        .map(|zero_position| get_paths(map, zero_position).len())
-        .sum()
-}
-
 fn get_paths(map: &Map, start: Position) -> Vec<Vec<Position>> {
     let mut paths = vec![];
     let mut stack = vec![(vec![start], start)];
@@ -66,40 +50,67 @@ fn get_paths(map: &Map, start: Position) -> Vec<Vec<Position>> {
     paths
 }
 
-fn parse_input(input: &str) -> Map {
-    let map = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect();
-    Map { map }
+struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let map = input
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(offset, c)| {
+                        c.to_digit(10).map(|height| height as usize).ok_or_else(|| {
+                            ParseError::at(
+                                input,
+                                &line[offset..],
+                                format!("expected a digit, found `{}`", c),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Map { map })
+    }
+
+    fn part_one(map: &mut Self::Parsed) -> impl Display {
+        get_zero_positions(map)
+            .map(|zero_position| {
+                get_paths(map, zero_position)
+                    .iter()
+                    .map(|p| p.last().unwrap())
+                    .unique()
+                    .count()
+            })
+            .sum::<usize>()
+    }
+
+    fn part_two(map: &mut Self::Parsed) -> impl Display {
+        get_zero_positions(map)
+            .map(|zero_position| get_paths(map, zero_position).len())
+            .sum::<usize>()
+    }
 }
+
 fn main() {
-    let input = get_input(10);
-    let map = parse_input(&input);
-    println!("Part one: {}", part_one(&map));
-    println!("Part two: {}", part_two(&map));
+    Runner::solve::<Day10>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(10);
-        let map = parse_input(&input);
-        assert_eq!(part_one(&map), 36);
+        check_solution::<Day10>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(10);
-        let map = parse_input(&input);
-        assert_eq!(part_two(&map), 81);
+        check_solution::<Day10>(Part::Two);
     }
 }
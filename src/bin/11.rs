@@ -1,7 +1,11 @@
 use core::str;
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
-use aoc24::input::get_input;
+use aoc24::{
+    parse::{parse_all, spaced, unsigned, ParseError},
+    runner::Runner,
+    Solution,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Action {
@@ -18,9 +22,9 @@ fn act(number: usize) -> Action {
         let b = chars[len / 2..].iter().collect::<String>().parse().unwrap();
         Action::Split(a, b)
     } else if number != 0 {
-        return Action::Multiply;
+        Action::Multiply
     } else {
-        return Action::AddOne;
+        Action::AddOne
     }
 }
 
@@ -46,41 +50,48 @@ fn solve(mut stones: HashMap<usize, usize>, steps: usize) -> usize {
     stones.values().sum()
 }
 
-fn part_one(stones: HashMap<usize, usize>) -> usize {
-    solve(stones, 25)
-}
+struct Day11;
 
-fn part_two(stones: HashMap<usize, usize>) -> usize {
-    solve(stones, 75)
-}
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    /// How many stones there are with each number
+    type Parsed = HashMap<usize, usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut result = HashMap::new();
+        for number in parse_all(spaced(unsigned), input)? {
+            result.get_mut(&number).map(|v| *v += 1).unwrap_or_else(|| {
+                result.insert(number, 1);
+            });
+        }
+        Ok(result)
+    }
 
-fn parse_input(input: &str) -> HashMap<usize, usize> {
-    let mut result = HashMap::new();
-    for number in input.split_whitespace() {
-        let number = number.parse().unwrap();
-        result.get_mut(&number).map(|v| *v += 1).unwrap_or_else(|| {
-            result.insert(number, 1);
-        });
+    fn part_one(stones: &mut Self::Parsed) -> impl Display {
+        solve(stones.clone(), 25)
+    }
+
+    fn part_two(stones: &mut Self::Parsed) -> impl Display {
+        solve(stones.clone(), 75)
     }
-    result
 }
 
 fn main() {
-    let input = get_input(11);
-    let stones = parse_input(&input);
-    println!("Part One: {}", part_one(stones.clone()));
-    println!("Part Two: {}", part_two(stones));
+    Runner::solve::<Day11>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(11);
-        let stones = parse_input(&input);
-        assert_eq!(part_one(stones), 55312);
+        check_solution::<Day11>(Part::One);
+    }
+
+    #[test]
+    fn test_part_two() {
+        check_solution::<Day11>(Part::Two);
     }
 }
@@ -1,6 +1,10 @@
-use aoc24::input::get_input;
+use aoc24::{
+    parse::{grid, parse_all, ParseError},
+    runner::Runner,
+    Solution,
+};
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
 fn get_neighbors(i: usize, j: usize, garden: &[Vec<char>]) -> Vec<(usize, usize)> {
     let mut neighbors = Vec::new();
@@ -88,18 +92,6 @@ fn calculate_perimeter(positions: &[(usize, usize)], garden: &[Vec<char>]) -> us
         .sum()
 }
 
-fn part_one(garden: &[Vec<char>]) -> usize {
-    let areas = get_areas(garden);
-    areas
-        .iter()
-        .map(|positions| {
-            let surface = positions.len();
-            let perimeter = calculate_perimeter(positions, garden);
-            surface * perimeter
-        })
-        .sum()
-}
-
 fn count_segments(positions: &[(usize, usize)], garden: &[Vec<char>], is_row: bool) -> usize {
     // Get unique indices per row or column
     let unique_indices = if is_row {
@@ -167,47 +159,70 @@ fn count_segments(positions: &[(usize, usize)], garden: &[Vec<char>], is_row: bo
         .sum::<usize>()
 }
 
-fn part_two(garden: &[Vec<char>]) -> usize {
-    let areas = get_areas(garden);
-    areas
-        .iter()
-        .map(|positions| {
-            let segments_top_bottom = count_segments(positions, garden, true);
-            let segments_left_right = count_segments(positions, garden, false);
-            let surface = positions.len();
-            let perimeter = segments_top_bottom + segments_left_right;
-            surface * perimeter
-        })
-        .sum()
+/// The plots of the garden and the areas of plants they form, which both parts need
+struct Garden {
+    plots: Vec<Vec<char>>,
+    areas: Option<Vec<Vec<(usize, usize)>>>,
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
+struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed = Garden;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(Garden {
+            plots: parse_all(grid(|c| c), input)?,
+            areas: None,
+        })
+    }
+
+    fn part_one(garden: &mut Self::Parsed) -> impl Display {
+        let Garden { plots, areas } = garden;
+        areas
+            .get_or_insert_with(|| get_areas(plots))
+            .iter()
+            .map(|positions| {
+                let surface = positions.len();
+                let perimeter = calculate_perimeter(positions, plots);
+                surface * perimeter
+            })
+            .sum::<usize>()
+    }
+
+    fn part_two(garden: &mut Self::Parsed) -> impl Display {
+        let Garden { plots, areas } = garden;
+        areas
+            .get_or_insert_with(|| get_areas(plots))
+            .iter()
+            .map(|positions| {
+                let segments_top_bottom = count_segments(positions, plots, true);
+                let segments_left_right = count_segments(positions, plots, false);
+                let surface = positions.len();
+                let perimeter = segments_top_bottom + segments_left_right;
+                surface * perimeter
+            })
+            .sum::<usize>()
+    }
 }
 
 fn main() {
-    let input = get_input(12);
-    let garden = parse_input(&input);
-    println!("Part one: {}", part_one(&garden));
-    println!("Part two: {}", part_two(&garden));
+    Runner::solve::<Day12>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(12);
-        let garden = parse_input(&input);
-        assert_eq!(part_one(&garden), 1930);
+        check_solution::<Day12>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(12);
-        let garden = parse_input(&input);
-        assert_eq!(part_two(&garden), 1206);
+        check_solution::<Day12>(Part::Two);
     }
 }
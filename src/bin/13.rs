@@ -1,5 +1,16 @@
-use aoc24::input::get_input;
+use std::fmt::Display;
+
+use aoc24::{
+    parse::{blocks, labelled_coordinates, parse_all, ParseError},
+    runner::Runner,
+    Solution,
+};
 use glam::{DMat2, DVec2};
+use nom::{
+    character::complete::line_ending,
+    sequence::{terminated, tuple},
+    Parser,
+};
 
 #[derive(Debug)]
 struct Game {
@@ -24,70 +35,58 @@ fn solve(games: &[Game]) -> usize {
         .sum::<f64>() as usize
 }
 
-fn part_one(games: &[Game]) -> usize {
-    solve(games)
-}
+struct Day13;
 
-fn part_two(games: &[Game]) -> usize {
-    // Add 10000000000000 to every prize coordinate
-    let games = games
-        .iter()
-        .map(|game| Game {
-            a: game.a,
-            b: game.b,
-            prize: game.prize + DVec2::new(10000000000000.0, 10000000000000.0),
-        })
-        .collect::<Vec<_>>();
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed = Vec<Game>;
 
-    solve(&games)
-}
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let vector = |label| labelled_coordinates(label).map(|(x, y)| DVec2::new(x, y));
+        let game = tuple((
+            terminated(vector("Button A"), line_ending),
+            terminated(vector("Button B"), line_ending),
+            vector("Prize"),
+        ))
+        .map(|(a, b, prize)| Game { a, b, prize });
+        parse_all(blocks(game), input)
+    }
 
-fn parse_input(input: &str) -> Vec<Game> {
-    let regexp = regex::Regex::new(r"X[+=](\d+), Y[+=](\d+)").unwrap();
-    input
-        .split("\n\n")
-        .map(|block| {
-            let results = regexp.captures_iter(block).collect::<Vec<_>>();
-            let a = DVec2::new(
-                results[0][1].parse().unwrap(),
-                results[0][2].parse().unwrap(),
-            );
-            let b = DVec2::new(
-                results[1][1].parse().unwrap(),
-                results[1][2].parse().unwrap(),
-            );
-            let prize = DVec2::new(
-                results[2][1].parse().unwrap(),
-                results[2][2].parse().unwrap(),
-            );
-            Game { a, b, prize }
-        })
-        .collect()
+    fn part_one(games: &mut Self::Parsed) -> impl Display {
+        solve(games)
+    }
+
+    fn part_two(games: &mut Self::Parsed) -> impl Display {
+        // Add 10000000000000 to every prize coordinate
+        let games = games
+            .iter()
+            .map(|game| Game {
+                a: game.a,
+                b: game.b,
+                prize: game.prize + DVec2::new(10000000000000.0, 10000000000000.0),
+            })
+            .collect::<Vec<_>>();
+
+        solve(&games)
+    }
 }
 
 fn main() {
-    let input = get_input(13);
-    let games = parse_input(&input);
-    println!("Part one: {}", part_one(&games));
-    println!("Part two: {}", part_two(&games));
+    Runner::solve::<Day13>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(13);
-        let games = parse_input(&input);
-        assert_eq!(part_one(&games), 480);
+        check_solution::<Day13>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(13);
-        let games = parse_input(&input);
-        assert_eq!(part_two(&games), 875318608908);
+        check_solution::<Day13>(Part::Two);
     }
 }
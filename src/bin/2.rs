@@ -1,15 +1,10 @@
-use aoc24::input::get_input;
+use std::fmt::Display;
 
-fn parse_input(input: &str) -> Vec<Vec<isize>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        })
-        .collect()
-}
+use aoc24::{
+    parse::{lines, parse_all, signed, spaced, ParseError},
+    runner::Runner,
+    Solution,
+};
 
 fn is_valid(row: &[isize]) -> bool {
     let diffs: Vec<_> = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
@@ -17,51 +12,54 @@ fn is_valid(row: &[isize]) -> bool {
         || diffs.iter().all(|diff| (-3..=-1).contains(diff))
 }
 
-fn part_one(input: &[Vec<isize>]) -> usize {
-    input.iter().filter(|row| is_valid(row)).count()
-}
+struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<Vec<isize>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_all(lines(spaced(signed)), input)
+    }
+
+    fn part_one(input: &mut Self::Parsed) -> impl Display {
+        input.iter().filter(|row| is_valid(row)).count()
+    }
 
-fn part_two(input: &[Vec<isize>]) -> usize {
-    input
-        .iter()
-        .filter(|row| {
-            for i in 0..row.len() {
-                let mut row_one_removed = row.to_vec();
-                row_one_removed.remove(i);
+    fn part_two(input: &mut Self::Parsed) -> impl Display {
+        input
+            .iter()
+            .filter(|row| {
+                for i in 0..row.len() {
+                    let mut row_one_removed = row.to_vec();
+                    row_one_removed.remove(i);
 
-                if is_valid(&row_one_removed) {
-                    return true;
+                    if is_valid(&row_one_removed) {
+                        return true;
+                    }
                 }
-            }
-            false
-        })
-        .count()
+                false
+            })
+            .count()
+    }
 }
 
 fn main() {
-    let input = parse_input(&get_input(2));
-    println!("Part one: {}", part_one(&input));
-    println!("Part two: {}", part_two(&input));
+    Runner::solve::<Day2>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(2);
-        let parsed = parse_input(&input);
-        let result = part_one(&parsed);
-        assert_eq!(result, 2);
+        check_solution::<Day2>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(2);
-        let parsed = parse_input(&input);
-        let result = part_two(&parsed);
-        assert_eq!(result, 4);
+        check_solution::<Day2>(Part::Two);
     }
 }
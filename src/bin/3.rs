@@ -1,5 +1,7 @@
-use aoc24::input::{get_input};
-use regex::Regex;
+use std::fmt::Display;
+
+use aoc24::{parse::ParseError, runner::Runner, Solution};
+use regex::{Match, Regex};
 
 #[derive(Debug, PartialEq, Clone)]
 enum Instruction {
@@ -8,79 +10,88 @@ enum Instruction {
     Do,
 }
 
-fn parse_muls(input: &str) -> Vec<Instruction> {
-    let re = Regex::new(r"mul\((\d+),(\d+)\)|(don't\(\))|(do\(\))").unwrap();
-    re.captures_iter(input)
-        .map(|cap| {
-            if let (Some(a), Some(b)) = (cap.get(1), cap.get(2)) {
-                return Instruction::Mul(a.as_str().parse().unwrap(), b.as_str().parse().unwrap());
-            }
-            if cap.get(3).is_some() {
-                return Instruction::Dont;
-            }
-            if cap.get(4).is_some() {
-                return Instruction::Do;
-            }
-            unreachable!("No match")
-        })
-        .collect()
-}
+struct Day3;
 
-fn part_one(instructions: &[Instruction]) -> usize {
-    instructions
-        .iter()
-        .filter_map(|instruction| match instruction {
-            Instruction::Mul(a, b) => Some(a * b),
-            _ => None,
-        })
-        .sum()
-}
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Parsed = Vec<Instruction>;
 
-fn part_two(instructions: &[Instruction]) -> usize {
-    let mut currently_dont = false;
-    let mut result = 0;
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let re = Regex::new(r"mul\((\d+),(\d+)\)|(don't\(\))|(do\(\))").unwrap();
+        let number = |number: Match| {
+            number.as_str().parse().map_err(|_| {
+                ParseError::at(
+                    input,
+                    &input[number.start()..],
+                    "the number is out of range",
+                )
+            })
+        };
+        re.captures_iter(input)
+            .map(|cap| {
+                if let (Some(a), Some(b)) = (cap.get(1), cap.get(2)) {
+                    return Ok(Instruction::Mul(number(a)?, number(b)?));
+                }
+                if cap.get(3).is_some() {
+                    return Ok(Instruction::Dont);
+                }
+                if cap.get(4).is_some() {
+                    return Ok(Instruction::Do);
+                }
+                unreachable!("No match")
+            })
+            .collect()
+    }
 
-    for instruction in instructions {
-        match instruction {
-            Instruction::Dont => {
-                currently_dont = true;
-            }
-            Instruction::Do => {
-                currently_dont = false;
-            }
-            Instruction::Mul(a, b) => {
-                if !currently_dont {
-                    result += a * b;
+    fn part_one(instructions: &mut Self::Parsed) -> impl Display {
+        instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Mul(a, b) => Some(a * b),
+                _ => None,
+            })
+            .sum::<usize>()
+    }
+
+    fn part_two(instructions: &mut Self::Parsed) -> impl Display {
+        let mut currently_dont = false;
+        let mut result = 0;
+
+        for instruction in instructions.iter() {
+            match instruction {
+                Instruction::Dont => {
+                    currently_dont = true;
+                }
+                Instruction::Do => {
+                    currently_dont = false;
+                }
+                Instruction::Mul(a, b) => {
+                    if !currently_dont {
+                        result += a * b;
+                    }
                 }
             }
         }
+        result
     }
-    result
 }
 
 fn main() {
-    let binding = get_input(3);
-    let input = parse_muls(&binding);
-    println!("Part one: {}", part_one(&input));
-    println!("Part two: {}", part_two(&input));
+    Runner::solve::<Day3>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(3);
-        let parsed = parse_muls(&input);
-        assert_eq!(part_one(&parsed), 161);
+        check_solution::<Day3>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(3);
-        let parsed = parse_muls(&input);
-        assert_eq!(part_two(&parsed), 48);
+        check_solution::<Day3>(Part::Two);
     }
 }
@@ -1,4 +1,10 @@
-use aoc24::input::get_input;
+use std::fmt::Display;
+
+use aoc24::{
+    parse::{grid, parse_all, ParseError},
+    runner::Runner,
+    Solution,
+};
 
 fn check_direction(x: usize, y: usize, dx: isize, dy: isize, input: &[Vec<char>]) -> bool {
     const SEQUENCE: &[char] = &['X', 'M', 'A', 'S'];
@@ -36,70 +42,68 @@ fn check_all_directions(x: usize, y: usize, input: &[Vec<char>]) -> usize {
         .count()
 }
 
-fn part_one(input: &[Vec<char>]) -> usize {
-    input.iter().enumerate().fold(0, |acc, (x, row)| {
-        acc + row.iter().enumerate().fold(0, |acc, (y, c)| {
-            if *c == 'X' {
-                return acc + check_all_directions(x, y, input);
-            }
-            acc
-        })
-    })
-}
+struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_all(grid(|c| c), input)
+    }
 
-fn part_two(input: &[Vec<char>]) -> usize {
-    let num_rows = input.len();
-    let num_cols = input[0].len();
-
-    (1..num_rows - 1).fold(0, |acc, x| {
-        acc + (1..num_cols - 1).fold(0, |acc, y| {
-            let c = input[x][y];
-            if c == 'A' {
-                let diagonals = [
-                    ((x - 1, y - 1), (x + 1, y + 1)),
-                    ((x - 1, y + 1), (x + 1, y - 1)),
-                ];
-                if diagonals.iter().all(|&((x1, y1), (x2, y2))| {
-                    (input[x1][y1] == 'M' && input[x2][y2] == 'S')
-                        || (input[x1][y1] == 'S' && input[x2][y2] == 'M')
-                }) {
-                    return acc + 1;
+    fn part_one(input: &mut Self::Parsed) -> impl Display {
+        input.iter().enumerate().fold(0, |acc, (x, row)| {
+            acc + row.iter().enumerate().fold(0, |acc, (y, c)| {
+                if *c == 'X' {
+                    return acc + check_all_directions(x, y, input);
                 }
-            }
-            acc
+                acc
+            })
         })
-    })
-}
+    }
+
+    fn part_two(input: &mut Self::Parsed) -> impl Display {
+        let num_rows = input.len();
+        let num_cols = input[0].len();
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+        (1..num_rows - 1).fold(0, |acc, x| {
+            acc + (1..num_cols - 1).fold(0, |acc, y| {
+                let c = input[x][y];
+                if c == 'A' {
+                    let diagonals = [
+                        ((x - 1, y - 1), (x + 1, y + 1)),
+                        ((x - 1, y + 1), (x + 1, y - 1)),
+                    ];
+                    if diagonals.iter().all(|&((x1, y1), (x2, y2))| {
+                        (input[x1][y1] == 'M' && input[x2][y2] == 'S')
+                            || (input[x1][y1] == 'S' && input[x2][y2] == 'M')
+                    }) {
+                        return acc + 1;
+                    }
+                }
+                acc
+            })
+        })
+    }
 }
 
 fn main() {
-    let input = get_input(4);
-    let parsed = parse_input(&input);
-    println!("Part one: {}", part_one(&parsed));
-    println!("Part two: {}", part_two(&parsed));
+    Runner::solve::<Day4>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(4);
-        let parsed = parse_input(&input);
-        let result = part_one(&parsed);
-        assert_eq!(result, 18);
+        check_solution::<Day4>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(4);
-        let parsed = parse_input(&input);
-        let result = part_two(&parsed);
-        assert_eq!(result, 9);
+        check_solution::<Day4>(Part::Two);
     }
 }
@@ -1,6 +1,15 @@
-use std::{cmp::Ordering, collections::HashMap, fmt::Debug};
-
-use aoc24::input::get_input;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Debug, Display},
+};
+
+use aoc24::{
+    parse::{blank_line, comma_separated, lines, parse_all, rule, unsigned, ParseError},
+    runner::Runner,
+    Solution,
+};
+use nom::sequence::separated_pair;
 
 #[derive(Clone)]
 struct Page {
@@ -46,94 +55,97 @@ impl PartialOrd for Page {
     }
 }
 
-fn parse_input(input: &str) -> (HashMap<usize, Page>, Vec<Vec<usize>>) {
-    let mut pages: HashMap<usize, Page> = HashMap::new();
-
-    let mut parts = input.split("\n\n");
-    let rules = parts.next().unwrap();
-    rules.lines().for_each(|rule| {
-        if let Some((left, right)) = rule.split_once('|') {
-            let left = left.trim().parse().unwrap();
-            let right = right.trim().parse().unwrap();
+/// The pages by number, with the ordering rules they are part of, and the updates
+type PrintQueue = (HashMap<usize, Page>, Vec<Vec<usize>>);
+
+struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Parsed = PrintQueue;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let (rules, updates): (Vec<(usize, usize)>, _) = parse_all(
+            separated_pair(
+                lines(rule(unsigned, unsigned)),
+                blank_line,
+                lines(comma_separated(unsigned)),
+            ),
+            input,
+        )?;
+
+        let mut pages: HashMap<usize, Page> = HashMap::new();
+        // Pages that are only ever printed last or have no rules at all still need an entry
+        let numbers = rules.iter().flat_map(|&(left, right)| [left, right]);
+        for number in numbers.chain(updates.iter().flatten().copied()) {
+            pages.entry(number).or_insert_with(|| Page {
+                number,
+                must_be_printed_before: vec![],
+            });
+        }
+        for (left, right) in rules {
             pages
-                .entry(left)
-                .or_insert_with(|| Page {
-                    number: left,
-                    must_be_printed_before: vec![],
-                })
+                .get_mut(&left)
+                .unwrap()
                 .must_be_printed_before
                 .push(right);
         }
-    });
-    let updates = parts.next().unwrap();
-    let updates = updates
-        .lines()
-        .map(|update| update.split(',').map(|num| num.parse().unwrap()).collect())
-        .collect::<Vec<Vec<usize>>>();
-
-    (pages, updates)
-}
 
-fn part_one(pages: &HashMap<usize, Page>, updates: &[Vec<usize>]) -> usize {
-    updates
-        .iter()
-        .filter_map(|update| {
-            let update_pages: Vec<_> = update.iter().map(|num| pages.get(num).unwrap()).collect();
-            // Check if update is sorted
-            match update_pages.windows(2).all(|window| window[0] <= window[1]) {
-                true => Some(update_pages[update_pages.len() / 2].number),
-                false => None,
-            }
-        })
-        .sum()
-}
+        Ok((pages, updates))
+    }
 
-fn part_two(pages: &HashMap<usize, Page>, updates: &mut [Vec<usize>]) -> usize {
-    updates
-        .iter_mut()
-        .filter_map(|update| {
-            let mut update_pages: Vec<_> = update
-                .iter()
-                .map(|num| pages.get(num).unwrap().clone())
-                .collect();
-            // Check if update is sorted
-            match update_pages.windows(2).all(|window| window[0] <= window[1]) {
-                true => None,
-                false => {
-                    update_pages.sort();
-                    Some(update_pages[update_pages.len() / 2].number)
+    fn part_one((pages, updates): &mut Self::Parsed) -> impl Display {
+        updates
+            .iter()
+            .filter_map(|update| {
+                let update_pages: Vec<_> =
+                    update.iter().map(|num| pages.get(num).unwrap()).collect();
+                // Check if update is sorted
+                match update_pages.windows(2).all(|window| window[0] <= window[1]) {
+                    true => Some(update_pages[update_pages.len() / 2].number),
+                    false => None,
                 }
-            }
-        })
-        .sum()
+            })
+            .sum::<usize>()
+    }
+
+    fn part_two((pages, updates): &mut Self::Parsed) -> impl Display {
+        updates
+            .iter()
+            .filter_map(|update| {
+                let mut update_pages: Vec<_> = update
+                    .iter()
+                    .map(|num| pages.get(num).unwrap().clone())
+                    .collect();
+                // Check if update is sorted
+                match update_pages.windows(2).all(|window| window[0] <= window[1]) {
+                    true => None,
+                    false => {
+                        update_pages.sort();
+                        Some(update_pages[update_pages.len() / 2].number)
+                    }
+                }
+            })
+            .sum::<usize>()
+    }
 }
 
 fn main() {
-    let input = get_input(5);
-    let (pages, updates) = parse_input(&input);
-
-    println!("Part one: {}", part_one(&pages, &updates));
-    println!("Part two: {}", part_two(&pages, &mut updates.clone()));
+    Runner::solve::<Day5>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(5);
-        let (pages, updates) = parse_input(&input);
-        let result = part_one(&pages, &updates);
-        assert_eq!(result, 143);
+        check_solution::<Day5>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(5);
-        let (pages, mut updates) = parse_input(&input);
-        let result = part_two(&pages, &mut updates);
-        assert_eq!(result, 123);
+        check_solution::<Day5>(Part::Two);
     }
 }
@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
-use aoc24::input::get_input;
+use aoc24::{parse::ParseError, runner::Runner, Solution};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -100,13 +100,24 @@ impl Map {
     }
 }
 
-fn part_one(map: &mut Map) -> usize {
-    let mut visited = HashSet::new();
-    visited.insert(map.guard.position);
-    while map.step() {
-        visited.insert(map.guard.position);
+/// The map and the positions and directions of the guard on its way out, which both parts need
+struct Lab {
+    map: Map,
+    path: Option<HashSet<(Position, Direction)>>,
+}
+
+impl Lab {
+    fn path(&mut self) -> &HashSet<(Position, Direction)> {
+        self.path.get_or_insert_with(|| {
+            let mut map = self.map.clone();
+            let mut visited = HashSet::new();
+            visited.insert((map.guard.position, map.guard.direction));
+            while map.step() {
+                visited.insert((map.guard.position, map.guard.direction));
+            }
+            visited
+        })
     }
-    visited.len()
 }
 
 fn is_loop(map: &mut Map) -> bool {
@@ -121,100 +132,111 @@ fn is_loop(map: &mut Map) -> bool {
     false
 }
 
-fn part_two(map: &mut Map) -> usize {
-    let start_position = map.guard.position;
-    let start_direction = map.guard.direction;
+struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Parsed = Lab;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut guard_position = None;
+        let tiles = input
+            .lines()
+            .enumerate()
+            .map(|(i, row)| {
+                row.char_indices()
+                    .enumerate()
+                    .map(|(j, (offset, tile))| match tile {
+                        '.' => Ok(Tile::Empty),
+                        '#' => Ok(Tile::Obstacle),
+                        '^' => {
+                            guard_position = Some((i, j));
+                            Ok(Tile::Empty)
+                        }
+                        _ => Err(ParseError::at(
+                            input,
+                            &row[offset..],
+                            format!("invalid tile `{}`", tile),
+                        )),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let Some(position) = guard_position else {
+            return Err(ParseError::at(
+                input,
+                &input[input.len()..],
+                "the map has no guard `^`",
+            ));
+        };
+        let map = Map::new(
+            tiles,
+            Guard {
+                position,
+                direction: Direction::Up,
+            },
+        );
+        Ok(Lab { map, path: None })
+    }
 
-    let mut visited = HashSet::new();
-    visited.insert((map.guard.position, map.guard.direction));
-    while map.step() {
-        visited.insert((map.guard.position, map.guard.direction));
+    fn part_one(lab: &mut Self::Parsed) -> impl Display {
+        lab.path()
+            .iter()
+            .map(|(position, _)| position)
+            .collect::<HashSet<_>>()
+            .len()
     }
 
-    visited
-        .par_iter()
-        .filter_map(|(pos, direction)| {
-            let mut map = map.clone();
-            map.guard.position = start_position;
-            map.guard.direction = start_direction;
-            let new_obstacle_position = direction.update_position(
-                pos.0,
-                pos.1,
-                map.tiles.len() - 1,
-                map.tiles[0].len() - 1,
-            );
-            if let Some(new_obstacle_position) = new_obstacle_position {
-                if new_obstacle_position == start_position {
-                    return None;
-                }
+    fn part_two(lab: &mut Self::Parsed) -> impl Display {
+        let path = lab.path().clone();
+        let start_position = lab.map.guard.position;
+
+        path.par_iter()
+            .filter_map(|(pos, direction)| {
+                let mut map = lab.map.clone();
+                let new_obstacle_position = direction.update_position(
+                    pos.0,
+                    pos.1,
+                    map.tiles.len() - 1,
+                    map.tiles[0].len() - 1,
+                );
+                if let Some(new_obstacle_position) = new_obstacle_position {
+                    if new_obstacle_position == start_position {
+                        return None;
+                    }
 
-                if map.tiles[new_obstacle_position.0][new_obstacle_position.1] == Tile::Empty {
-                    map.tiles[new_obstacle_position.0][new_obstacle_position.1] = Tile::Obstacle;
-                    if is_loop(&mut map) {
-                        return Some(new_obstacle_position);
+                    if map.tiles[new_obstacle_position.0][new_obstacle_position.1] == Tile::Empty {
+                        map.tiles[new_obstacle_position.0][new_obstacle_position.1] =
+                            Tile::Obstacle;
+                        if is_loop(&mut map) {
+                            return Some(new_obstacle_position);
+                        }
                     }
                 }
-            }
-            None
-        })
-        .collect::<HashSet<_>>()
-        .len()
+                None
+            })
+            .collect::<HashSet<_>>()
+            .len()
+    }
 }
 
-fn parse_input(input: &str) -> Map {
-    let mut guard_position = None;
-    let tiles = input
-        .lines()
-        .enumerate()
-        .map(|(i, row)| {
-            row.chars()
-                .enumerate()
-                .map(|(j, tile)| match tile {
-                    '.' => Tile::Empty,
-                    '#' => Tile::Obstacle,
-                    '^' => {
-                        guard_position = Some((i, j));
-                        Tile::Empty
-                    }
-                    _ => panic!("Invalid tile!"),
-                })
-                .collect()
-        })
-        .collect();
-
-    Map::new(
-        tiles,
-        Guard {
-            position: guard_position.unwrap(),
-            direction: Direction::Up,
-        },
-    )
-}
 fn main() {
-    let input = get_input(6);
-    let mut map = parse_input(&input);
-    println!("Part one: {}", part_one(&mut map.clone()));
-    println!("Part two: {}", part_two(&mut map));
+    Runner::solve::<Day6>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(6);
-        let mut map = parse_input(&input);
-        let result = part_one(&mut map);
-        assert_eq!(result, 41);
+        check_solution::<Day6>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(6);
-        let mut map = parse_input(&input);
-        let result = part_two(&mut map);
-        assert_eq!(result, 6);
+        check_solution::<Day6>(Part::Two);
     }
 }
@@ -1,4 +1,10 @@
-use aoc24::input::get_input;
+use std::fmt::Display;
+
+use aoc24::{
+    parse::{key_values, lines, parse_all, spaced, unsigned, ParseError},
+    runner::Runner,
+    Solution,
+};
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
@@ -61,54 +67,47 @@ fn solve(available_operators: Vec<Operator>, equations: &[Equation]) -> usize {
         .sum()
 }
 
-fn part_one(equations: &[Equation]) -> usize {
-    let available_operators = vec![Operator::Add, Operator::Multiply];
-    solve(available_operators, equations)
-}
+struct Day7;
 
-fn part_two(equations: &[Equation]) -> usize {
-    let available_operators = vec![Operator::Add, Operator::Multiply, Operator::Concatenate];
-    solve(available_operators, equations)
-}
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Parsed = Vec<Equation>;
 
-fn parse_input(input: &str) -> Vec<Equation> {
-    input
-        .lines()
-        .map(|line| {
-            let (result, number_str) = line.split_once(": ").unwrap();
-            let numbers = number_str.split(' ').map(|n| n.parse().unwrap()).collect();
-            Equation {
-                result: result.parse().unwrap(),
-                numbers,
-            }
-        })
-        .collect()
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(
+            parse_all(lines(key_values(unsigned, spaced(unsigned))), input)?
+                .into_iter()
+                .map(|(result, numbers)| Equation { result, numbers })
+                .collect(),
+        )
+    }
+
+    fn part_one(equations: &mut Self::Parsed) -> impl Display {
+        let available_operators = vec![Operator::Add, Operator::Multiply];
+        solve(available_operators, equations)
+    }
+
+    fn part_two(equations: &mut Self::Parsed) -> impl Display {
+        let available_operators = vec![Operator::Add, Operator::Multiply, Operator::Concatenate];
+        solve(available_operators, equations)
+    }
 }
 fn main() {
-    let input = get_input(7);
-    let equations = parse_input(&input);
-    println!("Part one: {}", part_one(&equations));
-    println!("Part two: {}", part_two(&equations));
+    Runner::solve::<Day7>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(7);
-        let equations = parse_input(&input);
-        let result = part_one(&equations);
-        assert_eq!(result, 3749);
+        check_solution::<Day7>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(7);
-        let equations = parse_input(&input);
-        let result = part_two(&equations);
-        assert_eq!(result, 11387);
+        check_solution::<Day7>(Part::Two);
     }
 }
@@ -1,6 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
-use aoc24::input::get_input;
+use aoc24::{
+    parse::{grid, parse_all, ParseError},
+    runner::Runner,
+    Solution,
+};
 use glam::IVec2;
 use itertools::Itertools;
 
@@ -11,29 +18,6 @@ struct Map {
     antennas: HashMap<char, Vec<IVec2>>,
 }
 
-fn parse_input(input: &str) -> Map {
-    let mut antennas = HashMap::new();
-    let mut width = 0;
-    let mut height = 0;
-    for (y, line) in input.lines().enumerate() {
-        height = y + 1;
-        for (x, c) in line.chars().enumerate() {
-            width = x + 1;
-            if c != '.' {
-                antennas
-                    .entry(c)
-                    .or_insert_with(Vec::new)
-                    .push(IVec2::new(x as i32, y as i32));
-            }
-        }
-    }
-    Map {
-        width,
-        height,
-        antennas,
-    }
-}
-
 fn is_in_bounds(antinode: IVec2, width: usize, height: usize) -> bool {
     (0..width as i32).contains(&antinode.x) && (0..height as i32).contains(&antinode.y)
 }
@@ -77,29 +61,57 @@ fn solve(map: &Map, part_one: bool) -> usize {
         .len()
 }
 
+struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let rows = parse_all(grid(|c| c), input)?;
+        let mut antennas = HashMap::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c != '.' {
+                    antennas
+                        .entry(c)
+                        .or_insert_with(Vec::new)
+                        .push(IVec2::new(x as i32, y as i32));
+                }
+            }
+        }
+        Ok(Map {
+            width: rows[0].len(),
+            height: rows.len(),
+            antennas,
+        })
+    }
+
+    fn part_one(map: &mut Self::Parsed) -> impl Display {
+        solve(map, true)
+    }
+
+    fn part_two(map: &mut Self::Parsed) -> impl Display {
+        solve(map, false)
+    }
+}
+
 fn main() {
-    let input = get_input(8);
-    let map = parse_input(&input);
-    println!("Part One: {}", solve(&map, true));
-    println!("Part Two: {}", solve(&map, false));
+    Runner::solve::<Day8>();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc24::input::get_example;
+    use aoc24::{testing::check_solution, Part};
 
     #[test]
     fn test_part_one() {
-        let input = get_example(8);
-        let map = parse_input(&input);
-        assert_eq!(solve(&map, true), 14);
+        check_solution::<Day8>(Part::One);
     }
 
     #[test]
     fn test_part_two() {
-        let input = get_example(8);
-        let map = parse_input(&input);
-        assert_eq!(solve(&map, false), 34);
+        check_solution::<Day8>(Part::Two);
     }
 }
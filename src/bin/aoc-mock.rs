@@ -0,0 +1,392 @@
+//! A stand-in for adventofcode.com that serves puzzle inputs, puzzle pages, answer verdicts and
+//! leaderboards from fixture files, so the fetch and submit flow can be tried without network.
+//! Point the other binaries at it with `AOC_BASE_URL`.
+//!
+//! Fixtures, relative to the fixture directory:
+//! - `session`: the only session cookie that is accepted
+//! - `user`: the name of the user the session belongs to
+//! - `{year}/inputs/{day}.txt`: the puzzle input. Days without one are treated as locked.
+//! - `{year}/puzzles/{day}.html`: the puzzle page, `{day}.part-two.html` once part one is solved
+//! - `{year}/answers/{day}.txt`: the correct answers, one line per part
+//! - `{year}/leaderboards/{id}.json`: a private leaderboard
+//! - `{year}/leaderboards/self.html`: the personal leaderboard
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
+
+const USAGE: &str = "\
+Usage: cargo mock [--port <port>] [--fixtures <dir>] [--rate-limit <seconds>] [--fail-first <n>]
+
+Options:
+    --port <port>           Port to listen on, 0 picks a free one (default: 0)
+    --fixtures <dir>        Directory holding the fixture files (default: tests/fixtures)
+    --rate-limit <seconds>  Time a wrong answer blocks further submissions (default: 0)
+    --fail-first <n>        Answer the first n requests with 503 Service Unavailable (default: 0)";
+
+const UNLOCK_MESSAGE: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+const LOGIN_MESSAGE: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, body: impl Into<String>) -> Response {
+        Response {
+            status,
+            content_type: "text/plain",
+            body: body.into(),
+        }
+    }
+
+    fn html(body: impl Into<String>) -> Response {
+        Response {
+            status: "200 OK",
+            content_type: "text/html",
+            body: body.into(),
+        }
+    }
+
+    fn not_found() -> Response {
+        Response::new("404 Not Found", "404 Not Found\n")
+    }
+}
+
+struct Server {
+    fixtures: PathBuf,
+    rate_limit: Duration,
+    /// Number of solved parts per year and day
+    solved: HashMap<(u16, u8), u8>,
+    /// No answers are checked before this time, set by wrong answers
+    blocked_until: Option<Instant>,
+    /// Number of requests that are still going to fail
+    fail_first: u32,
+}
+
+impl Server {
+    fn fixture(&self, path: impl AsRef<Path>) -> Option<String> {
+        fs::read_to_string(self.fixtures.join(path)).ok()
+    }
+
+    fn logged_in(&self, request: &Request) -> bool {
+        let session = self.fixture("session");
+        let session = session.as_deref().map(str::trim);
+        request.cookie.is_some() && request.cookie.as_deref() == session
+    }
+
+    fn handle(&mut self, request: &Request) -> Response {
+        if self.fail_first > 0 {
+            self.fail_first -= 1;
+            return Response::new("503 Service Unavailable", "503 Service Unavailable\n");
+        }
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        let parse_day = |year: &str, day: &str| Some((year.parse().ok()?, day.parse().ok()?));
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", [year]) if year.parse::<u16>().is_ok() => self.calendar(request, year),
+            ("GET", [year, "day", day, "input"]) => match parse_day(year, day) {
+                Some((year, day)) => self.input(request, year, day),
+                None => Response::not_found(),
+            },
+            ("GET", [year, "day", day]) => match parse_day(year, day) {
+                Some((year, day)) => self.puzzle(request, year, day),
+                None => Response::not_found(),
+            },
+            ("POST", [year, "day", day, "answer"]) => match parse_day(year, day) {
+                Some((year, day)) => self.answer(request, year, day),
+                None => Response::not_found(),
+            },
+            ("GET", [year, "leaderboard", "self"]) => self.personal_stats(request, year),
+            ("GET", [year, "leaderboard", "private", "view", file]) => {
+                self.leaderboard(request, year, file)
+            }
+            _ => Response::not_found(),
+        }
+    }
+
+    fn calendar(&self, request: &Request, year: &str) -> Response {
+        let user = match self.logged_in(request) {
+            true => {
+                let name = self.fixture("user").unwrap_or_default();
+                format!(
+                    "<div class=\"user\">{} <span class=\"star-count\">{}*</span></div>",
+                    name.trim(),
+                    self.solved.values().sum::<u8>()
+                )
+            }
+            false => format!("<div><a href=\"/{}/auth/login\">[Log In]</a></div>", year),
+        };
+        Response::html(format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\"><body><header>{}</header>\n<main><pre class=\"calendar\"></pre></main></body></html>\n",
+            user
+        ))
+    }
+
+    fn input(&self, request: &Request, year: u16, day: u8) -> Response {
+        if !self.logged_in(request) {
+            return Response::new("400 Bad Request", LOGIN_MESSAGE);
+        }
+        match self.fixture(format!("{}/inputs/{}.txt", year, day)) {
+            Some(input) => Response::new("200 OK", input),
+            None => Response::new("404 Not Found", UNLOCK_MESSAGE),
+        }
+    }
+
+    fn puzzle(&self, request: &Request, year: u16, day: u8) -> Response {
+        let solved = self.solved.get(&(year, day)).copied().unwrap_or(0);
+        let part_two = format!("{}/puzzles/{}.part-two.html", year, day);
+        let page = match self.logged_in(request) && solved >= 1 {
+            true => self.fixture(&part_two),
+            false => None,
+        };
+        match page.or_else(|| self.fixture(format!("{}/puzzles/{}.html", year, day))) {
+            Some(page) => Response::html(page),
+            None => Response::not_found(),
+        }
+    }
+
+    fn answer(&mut self, request: &Request, year: u16, day: u8) -> Response {
+        if !self.logged_in(request) {
+            return Response::new(
+                "400 Bad Request",
+                "To play, please identify yourself via one of these services.\n",
+            );
+        }
+        let Some(answers) = self.fixture(format!("{}/answers/{}.txt", year, day)) else {
+            return Response::new("404 Not Found", UNLOCK_MESSAGE);
+        };
+        let form = parse_form(&request.body);
+        let level: u8 = form
+            .get("level")
+            .and_then(|level| level.parse().ok())
+            .unwrap_or(0);
+        let answer = form.get("answer").map_or("", |answer| answer.trim());
+
+        let solved = self.solved.entry((year, day)).or_default();
+        if level != *solved + 1 {
+            return article(
+                "You don't seem to be solving the right level.  Did you already complete it?",
+            );
+        }
+        if let Some(remaining) = self
+            .blocked_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+        {
+            let seconds = remaining.as_secs() + 1;
+            let wait = match seconds >= 60 {
+                true => format!("{}m {}s", seconds / 60, seconds % 60),
+                false => format!("{}s", seconds),
+            };
+            return article(&format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {} left to wait.", wait));
+        }
+
+        let correct = answers.lines().nth(level as usize - 1).unwrap_or("").trim();
+        if answer == correct {
+            *solved += 1;
+            return article(
+                "That's the right answer!  You are one gold star closer to saving Christmas.",
+            );
+        }
+        self.blocked_until = Some(Instant::now() + self.rate_limit);
+        let hint = match (answer.parse::<i128>(), correct.parse::<i128>()) {
+            (Ok(answer), Ok(correct)) if answer > correct => "  your answer is too high.",
+            (Ok(answer), Ok(correct)) if answer < correct => "  your answer is too low.",
+            _ => "",
+        };
+        article(&format!(
+            "That's not the right answer;{}  Please wait before trying again.",
+            hint
+        ))
+    }
+
+    fn personal_stats(&self, request: &Request, year: &str) -> Response {
+        if !self.logged_in(request) {
+            return self.calendar(request, year);
+        }
+        match self.fixture(format!("{}/leaderboards/self.html", year)) {
+            Some(page) => Response::html(page),
+            None => Response::not_found(),
+        }
+    }
+
+    fn leaderboard(&self, request: &Request, year: &str, file: &str) -> Response {
+        if !self.logged_in(request) {
+            return Response::new("403 Forbidden", "403 Forbidden\n");
+        }
+        let Some(id) = file.strip_suffix(".json") else {
+            return Response::not_found();
+        };
+        match self.fixture(format!("{}/leaderboards/{}.json", year, id)) {
+            Some(json) => Response {
+                status: "200 OK",
+                content_type: "application/json",
+                body: json,
+            },
+            None => Response::not_found(),
+        }
+    }
+}
+
+fn article(text: &str) -> Response {
+    Response::html(format!("<!DOCTYPE html>\n<html lang=\"en-us\"><body><main>\n<article><p>{}</p></article>\n</main></body></html>\n", text))
+}
+
+/// Decodes an `application/x-www-form-urlencoded` body
+fn parse_form(body: &str) -> HashMap<String, String> {
+    let decode = |value: &str| {
+        let value = value.replace('+', " ");
+        let mut bytes = Vec::new();
+        let mut rest = value.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            let escaped = tail
+                .get(..2)
+                .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+            match (byte, escaped) {
+                (b'%', Some(escaped)) => {
+                    bytes.push(escaped);
+                    rest = &tail[2..];
+                }
+                _ => {
+                    bytes.push(byte);
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (decode(key), decode(value)))
+        .collect()
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "cookie" => {
+                cookie = value
+                    .split(';')
+                    .find_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .map(str::to_string)
+            }
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn respond(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let mut port = 0;
+    let mut server = Server {
+        fixtures: PathBuf::from("tests/fixtures"),
+        rate_limit: Duration::ZERO,
+        solved: HashMap::new(),
+        blocked_until: None,
+        fail_first: 0,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        match (arg.as_str(), value) {
+            ("--port", Some(value)) => {
+                port = value
+                    .parse()
+                    .unwrap_or_else(|_| exit_with_usage(&format!("Invalid port: {}", value)))
+            }
+            ("--fixtures", Some(value)) => server.fixtures = PathBuf::from(value),
+            ("--rate-limit", Some(value)) => {
+                server.rate_limit = value
+                    .parse()
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|_| exit_with_usage(&format!("Invalid rate limit: {}", value)))
+            }
+            ("--fail-first", Some(value)) => {
+                server.fail_first = value
+                    .parse()
+                    .unwrap_or_else(|_| exit_with_usage(&format!("Invalid count: {}", value)))
+            }
+            _ => exit_with_usage(&format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|err| {
+        eprintln!("Could not listen on port {}: {}", port, err);
+        process::exit(1);
+    });
+    let address = listener.local_addr().unwrap();
+    // Tests read this line to find out which port was picked
+    println!("Listening on http://{}", address);
+    io::stdout().flush().ok();
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let result = read_request(&stream).and_then(|request| {
+            let response = server.handle(&request);
+            eprintln!("{} {} -> {}", request.method, request.path, response.status);
+            respond(&stream, &response)
+        });
+        if let Err(err) = result {
+            eprintln!("Error handling request: {}", err);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    process,
+};
+
+use aoc24::{
+    cache,
+    config::{self, UserConfig},
+    example::{self, ExpectedAnswers},
+    input, leaderboard,
+    ledger::{Ledger, Rejection},
+    puzzle, schedule, session, stats, submit, Part,
+};
+
+const USAGE: &str = "\
+Usage: cargo aoc [--profile <name>] <command>
+
+Commands:
+    fetch <day> [year] [--wait]
+                          Download the input of a day. With --wait, wait for the day to unlock
+    prefetch [year]       Download the inputs of all unlocked days that are not cached yet
+    example <day> [year] [--list] [--block <n>] [--name <name>] [--force]
+                          Download the example and its expected answers from the puzzle page.
+                          --list shows all code blocks, --block picks one of them and --name
+                          stores it under another name than `default`
+    puzzle <day> [year] [--refresh]
+                          Download the puzzle description as Markdown to data/{year}/puzzles/.
+                          --refresh downloads it again if it only contains part one
+    examples <day> [year] List the examples of a day with their expected answers
+    submit <day> <part> <answer> [year]
+                          Submit an answer unless the ledger already knows it is wrong
+    check <day> <part> <answer> [year]
+                          Check an answer against the ledger without submitting it
+    session set [cookie]  Store the session cookie in ~/.config/aoc24/config.toml. Reads it from
+                          stdin if it is not given
+    session check         Check that the session cookie is accepted and show the user
+    session expiry        Show how long the session cookie stays valid
+    profiles [year]       List the profiles with their stored sessions and cached inputs
+    leaderboard [id] [year]
+                          Show a private leaderboard, by default the one in AOC_LEADERBOARD. It
+                          is fetched at most every 15 minutes
+    stats [year] [--refresh]
+                          Show the times and ranks of the personal leaderboard next to the
+                          runtimes of the solutions. The leaderboard is downloaded once and
+                          again with --refresh
+    migrate [year]        Move inputs and examples from data/{inputs,examples} into data/{year}/
+                          and single examples into per-day example directories
+    verify-cache [year] [--refetch]
+                          Check the cached inputs against the checksums recorded when they were
+                          downloaded and offer to download modified or truncated ones again
+    clean-cache [year]    Remove cached inputs that are error pages instead of puzzle inputs";
+
+fn parse_year(arg: Option<&String>) -> Result<u16, String> {
+    match arg {
+        Some(year) => year.parse().map_err(|_| format!("Invalid year: {}", year)),
+        None => config::year().map_err(|err| err.to_string()),
+    }
+}
+
+fn migrate(args: &[String]) -> Result<(), String> {
+    let year = parse_year(args.first())?;
+    let mut moved = input::migrate_flat_cache(year).map_err(|err| err.to_string())?;
+    moved.extend(example::migrate_single_examples(year).map_err(|err| err.to_string())?);
+    for path in &moved {
+        println!("Moved {}", path.display());
+    }
+    println!(
+        "Migrated {} files into {}",
+        moved.len(),
+        config::year_dir(year).display()
+    );
+    Ok(())
+}
+
+fn fetch(args: &[String]) -> Result<(), String> {
+    let wait = args.iter().any(|arg| arg == "--wait");
+    let args: Vec<_> = args.iter().filter(|arg| *arg != "--wait").collect();
+    let day = args
+        .first()
+        .ok_or(USAGE)?
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", args[0]))?;
+    let year = parse_year(args.get(1).copied())?;
+    let input = match wait {
+        true => input::get_input_when_unlocked(year, day),
+        false => input::get_input_for(year, day),
+    }
+    .map_err(|err| err.to_string())?;
+    println!(
+        "Fetched day {} of {} ({} lines)",
+        day,
+        year,
+        input.lines().count()
+    );
+    Ok(())
+}
+
+/// Formats days as a list of ranges, e.g. `1-3, 5`
+fn format_days(days: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    let ranges: Vec<_> = ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect();
+    match ranges.is_empty() {
+        true => "none".to_string(),
+        false => ranges.join(", "),
+    }
+}
+
+fn prefetch(args: &[String]) -> Result<(), String> {
+    let year = parse_year(args.first())?;
+    let summary = input::prefetch_inputs(year).map_err(|err| err.to_string())?;
+    println!("Fetched: {}", format_days(&summary.fetched));
+    println!("Cached:  {}", format_days(&summary.cached));
+    println!("Locked:  {}", format_days(&summary.locked));
+    Ok(())
+}
+
+fn example(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let (mut list, mut force, mut block) = (false, false, None);
+    let mut name = example::DEFAULT_EXAMPLE.to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => list = true,
+            "--force" => force = true,
+            "--block" => {
+                let index = args
+                    .next()
+                    .ok_or("--block expects the index of a code block")?;
+                block = Some(
+                    index
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid block: {}", index))?,
+                );
+            }
+            "--name" => name = args.next().ok_or("--name expects a name")?.clone(),
+            _ => positional.push(arg),
+        }
+    }
+    let day: u8 = positional
+        .first()
+        .ok_or(USAGE)?
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", positional[0]))?;
+    let year = parse_year(positional.get(1).copied())?;
+
+    let page = puzzle::fetch_puzzle_page(year, day).map_err(|err| err.to_string())?;
+    let articles = puzzle::parse_articles(&page);
+    let article = articles
+        .first()
+        .ok_or("Could not find the puzzle description")?;
+
+    if list {
+        for (index, code) in article.code_blocks.iter().enumerate() {
+            let marker = match article.example_block == Some(index) {
+                true => " (default)",
+                false => "",
+            };
+            println!("--- Block {}{} ---\n{}", index, marker, code);
+        }
+        return Ok(());
+    }
+
+    let example = match block {
+        Some(index) => article.code_blocks.get(index).ok_or_else(|| {
+            format!(
+                "There are only {} code blocks, see --list",
+                article.code_blocks.len()
+            )
+        })?,
+        None => article
+            .default_example()
+            .ok_or("The puzzle description has no code blocks")?,
+    };
+    let answers = ExpectedAnswers {
+        part_one: article.example_answer.clone(),
+        part_two: articles
+            .get(1)
+            .and_then(|part_two| part_two.example_answer.clone()),
+    };
+    let path = example::example_path(year, day, &name);
+    if !puzzle::save_example(&path, example, &answers, force).map_err(|err| err.to_string())? {
+        return Err(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        ));
+    }
+    println!("Saved the example to {}", path.display());
+    for part in [Part::One, Part::Two] {
+        match answers.get(part) {
+            Some(answer) => println!("Expected answer of part {}: {}", part, answer),
+            None => println!("No expected answer found for part {}", part),
+        }
+    }
+    Ok(())
+}
+
+fn examples(args: &[String]) -> Result<(), String> {
+    let day: u8 = args
+        .first()
+        .ok_or(USAGE)?
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", args[0]))?;
+    let year = parse_year(args.get(1))?;
+    let names = example::list_examples(year, day).map_err(|err| err.to_string())?;
+    if names.is_empty() {
+        println!("Day {} has no examples yet", day);
+    }
+    for name in names {
+        let example = example::load_example(year, day, &name).map_err(|err| err.to_string())?;
+        println!(
+            "{}: {} lines, part one: {}, part two: {}",
+            name,
+            example.input.lines().count(),
+            example.answers.part_one.as_deref().unwrap_or("?"),
+            example.answers.part_two.as_deref().unwrap_or("?"),
+        );
+    }
+    Ok(())
+}
+
+fn puzzle(args: &[String]) -> Result<(), String> {
+    let refresh = args.iter().any(|arg| arg == "--refresh");
+    let args: Vec<_> = args.iter().filter(|arg| *arg != "--refresh").collect();
+    let day = args
+        .first()
+        .ok_or(USAGE)?
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", args[0]))?;
+    let year = parse_year(args.get(1).copied())?;
+    puzzle::get_puzzle(year, day, refresh).map_err(|err| err.to_string())?;
+    println!("{}", puzzle::puzzle_path(year, day).display());
+    Ok(())
+}
+
+fn parse_answer_args(args: &[String]) -> Result<(u8, Part, &str, u16), String> {
+    let [day, part, answer, rest @ ..] = args else {
+        return Err(USAGE.to_string());
+    };
+    let day = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+    let part = part
+        .parse()
+        .ok()
+        .and_then(Part::from_level)
+        .ok_or_else(|| format!("Invalid part: {}", part))?;
+    let year = parse_year(rest.first())?;
+    Ok((day, part, answer, year))
+}
+
+fn submit(args: &[String]) -> Result<(), String> {
+    let (day, part, answer, year) = parse_answer_args(args)?;
+    let submission =
+        submit::submit_answer(year, day, part, answer).map_err(|err| err.to_string())?;
+    match submission.refreshed {
+        Some(Ok(written)) => {
+            for path in written {
+                println!("Updated {}", path.display());
+            }
+        }
+        Some(Err(err)) => eprintln!("Warning: could not fetch the example of part two: {}", err),
+        None => {}
+    }
+    println!("{}", submission.outcome);
+    Ok(())
+}
+
+fn check(args: &[String]) -> Result<(), String> {
+    let (day, part, answer, year) = parse_answer_args(args)?;
+    let ledger = Ledger::load(year, day).map_err(|err| err.to_string())?;
+    match ledger.check(part, answer) {
+        Ok(()) => println!("Nothing is known about {} yet", answer),
+        Err(Rejection::AlreadySolved { accepted }) if accepted == *answer => {
+            println!("{} is the accepted answer", answer)
+        }
+        Err(reason) => println!("{} is wrong: {}", answer, reason),
+    }
+    Ok(())
+}
+
+fn session(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("set") => session_set(args.get(1)),
+        Some("check") => {
+            let session = config::session().map_err(|err| err.to_string())?;
+            let user = session::check_session(&session).map_err(|err| err.to_string())?;
+            println!("Logged in as {}", user);
+            Ok(())
+        }
+        Some("expiry") => session_expiry(),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn session_set(cookie: Option<&String>) -> Result<(), String> {
+    let cookie = match cookie {
+        Some(cookie) => cookie.clone(),
+        None => {
+            eprint!("Paste the session cookie: ");
+            io::stderr().flush().ok();
+            let mut line = String::new();
+            io::stdin()
+                .read_line(&mut line)
+                .map_err(|err| err.to_string())?;
+            line
+        }
+    };
+    let cookie = cookie.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+    if cookie.is_empty() {
+        return Err("The session cookie is empty".to_string());
+    }
+
+    let profile = config::profile().map_err(|err| err.to_string())?;
+    let mut user_config = UserConfig::load().map_err(|err| err.to_string())?;
+    user_config.set_session(profile.as_deref(), cookie);
+    let path = user_config.save().map_err(|err| err.to_string())?;
+    println!("Stored the session cookie in {}", path.display());
+    if profile.is_none() && config::var("AOC_SESSION").is_some() {
+        eprintln!(
+            "Warning: AOC_SESSION is set in the environment or .env file and takes precedence"
+        );
+    }
+    Ok(())
+}
+
+fn session_expiry() -> Result<(), String> {
+    let profile = config::profile().map_err(|err| err.to_string())?;
+    let user_config = UserConfig::load().map_err(|err| err.to_string())?;
+    let stored = user_config.session(profile.as_deref());
+    let Some(age) = stored.and_then(session::session_age) else {
+        return Err(
+            "Unknown, the session cookie was not stored with `cargo aoc session set`".to_string(),
+        );
+    };
+    match session::SESSION_LIFETIME.checked_sub(age) {
+        Some(remaining) if remaining > session::EXPIRY_WARNING => println!(
+            "The session cookie was stored {} ago and expires in about {}",
+            schedule::format_duration(age),
+            schedule::format_duration(remaining)
+        ),
+        Some(remaining) => eprintln!(
+            "Warning: the session cookie expires in about {}. Log in again and run `cargo aoc session set`",
+            schedule::format_duration(remaining)
+        ),
+        None => eprintln!(
+            "Warning: the session cookie was stored {} ago and has probably expired. Log in again and run `cargo aoc session set`",
+            schedule::format_duration(age)
+        ),
+    }
+    Ok(())
+}
+
+fn profiles(args: &[String]) -> Result<(), String> {
+    let year = parse_year(args.first())?;
+    let user_config = UserConfig::load().map_err(|err| err.to_string())?;
+    for profile in config::profiles(year).map_err(|err| err.to_string())? {
+        let inputs = fs::read_dir(input::inputs_dir(year, profile.as_deref()))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+                    .count()
+            })
+            .unwrap_or(0);
+        let session = match user_config.session(profile.as_deref()) {
+            Some(_) => "session stored",
+            None => "no stored session",
+        };
+        println!(
+            "{}: {}, {} cached inputs",
+            profile.as_deref().unwrap_or("default"),
+            session,
+            inputs
+        );
+    }
+    Ok(())
+}
+
+fn leaderboard(args: &[String]) -> Result<(), String> {
+    let id = args
+        .first()
+        .cloned()
+        .or_else(|| config::var("AOC_LEADERBOARD"))
+        .ok_or("Pass the id of the leaderboard or set AOC_LEADERBOARD")?;
+    let id = id
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid leaderboard id: {}", id))?;
+    let year = parse_year(args.get(1))?;
+    let (board, age) = leaderboard::get_leaderboard(year, id).map_err(|err| err.to_string())?;
+    println!(
+        "Private leaderboard {} of {}, fetched {} ago\n",
+        id,
+        year,
+        schedule::format_duration(age)
+    );
+    print!("{}", leaderboard::render(&board, year));
+    Ok(())
+}
+
+fn stats(args: &[String]) -> Result<(), String> {
+    let refresh = args.iter().any(|arg| arg == "--refresh");
+    let args: Vec<_> = args.iter().filter(|arg| *arg != "--refresh").collect();
+    let year = parse_year(args.first().copied())?;
+    let profile = config::profile().map_err(|err| err.to_string())?;
+    let path = stats::personal_stats_path(year, profile.as_deref());
+    let cached = stats::load_json(&path).map_err(|err| err.to_string())?;
+    let personal = match cached {
+        Some(personal) if !refresh => personal,
+        _ => {
+            let personal = stats::fetch_personal_stats(year).map_err(|err| err.to_string())?;
+            stats::save_json(&path, &personal).map_err(|err| err.to_string())?;
+            personal
+        }
+    };
+    let runtimes = stats::load_json(&stats::runtimes_path(year, profile.as_deref()))
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+    print!("{}", stats::render(&personal, &runtimes));
+    Ok(())
+}
+
+/// Asks a yes/no question on the terminal. Without a terminal the answer is no.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("{} [y/N] ", question);
+    io::stderr().flush().ok();
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).is_ok() && line.trim().eq_ignore_ascii_case("y")
+}
+
+fn verify_cache(args: &[String]) -> Result<(), String> {
+    let refetch = args.iter().any(|arg| arg == "--refetch");
+    let args: Vec<_> = args.iter().filter(|arg| *arg != "--refetch").collect();
+    let year = parse_year(args.first().copied())?;
+    let profile = config::profile().map_err(|err| err.to_string())?;
+    let statuses = cache::verify_inputs(year, profile.as_deref()).map_err(|err| err.to_string())?;
+    for (day, status) in &statuses {
+        println!("Day {}: {}", day, status);
+    }
+    let broken: Vec<u8> = statuses
+        .iter()
+        .filter(|(_, status)| status.is_broken())
+        .map(|(day, _)| *day)
+        .collect();
+    if broken.is_empty() {
+        println!("All {} cached inputs are fine", statuses.len());
+        return Ok(());
+    }
+
+    let question = format!("Download {} inputs again?", broken.len());
+    if !refetch && !confirm(&question) {
+        return Err(format!(
+            "{} cached inputs are broken, run `cargo aoc verify-cache --refetch` to download them again",
+            broken.len()
+        ));
+    }
+    // The download replaces the cached file only once it succeeded
+    for day in broken {
+        input::download_input(year, day).map_err(|err| err.to_string())?;
+        println!("Downloaded day {} again", day);
+    }
+    Ok(())
+}
+
+fn clean_cache(args: &[String]) -> Result<(), String> {
+    let year = parse_year(args.first())?;
+    let removed = input::remove_invalid_inputs(year).map_err(|err| err.to_string())?;
+    for (path, reason) in &removed {
+        println!("Removed {}: {}", path.display(), reason);
+    }
+    println!(
+        "Removed {} invalid inputs from {}",
+        removed.len(),
+        config::year_dir(year).display()
+    );
+    Ok(())
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // Selects the profile for everything that follows, like setting AOC_PROFILE
+    if let Some(index) = args.iter().position(|arg| arg == "--profile") {
+        let Some(profile) = args.get(index + 1).cloned() else {
+            eprintln!("--profile expects the name of a profile");
+            process::exit(2);
+        };
+        env::set_var("AOC_PROFILE", profile);
+        args.drain(index..index + 2);
+    }
+    let result = match args.first().map(String::as_str) {
+        Some("fetch") => fetch(&args[1..]),
+        Some("prefetch") => prefetch(&args[1..]),
+        Some("example") => example(&args[1..]),
+        Some("puzzle") => puzzle(&args[1..]),
+        Some("examples") => examples(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("migrate") => migrate(&args[1..]),
+        Some("session") => session(&args[1..]),
+        Some("profiles") => profiles(&args[1..]),
+        Some("leaderboard") => leaderboard(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("verify-cache") => verify_cache(&args[1..]),
+        Some("clean-cache") => clean_cache(&args[1..]),
+        Some("--help" | "-h" | "help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,183 @@
+//! Integrity of the cached files. Files are written to a temporary file first and then renamed,
+//! so an interrupted run never leaves a half written file behind, and the checksums of
+//! downloaded inputs are recorded in a manifest to find inputs that changed afterwards.
+
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::input::{self, io_error, InputError};
+
+/// Name of the manifest in every inputs directory, in the format of `sha256sum`
+pub const MANIFEST: &str = "SHA256SUMS";
+
+/// Writes `content` to `path` by writing a temporary file next to it and renaming it
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), InputError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error(dir))?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".{}.tmp", process::id()));
+    let temp = PathBuf::from(temp);
+
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if let Err(err) = result {
+        fs::remove_file(&temp).ok();
+        return Err(io_error(path)(err));
+    }
+    Ok(())
+}
+
+/// The SHA-256 checksum of `content` as a hex string
+pub fn sha256(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The checksums of the files in one directory, by file name
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    path: PathBuf,
+    checksums: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Loads the manifest of `dir`. A missing manifest is an empty one.
+    pub fn load(dir: &Path) -> Result<Manifest, InputError> {
+        let path = dir.join(MANIFEST);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(io_error(path)(err)),
+        };
+        let checksums = content
+            .lines()
+            .filter_map(|line| {
+                let (checksum, file) = line.split_once("  ")?;
+                Some((file.to_string(), checksum.to_string()))
+            })
+            .collect();
+        Ok(Manifest { path, checksums })
+    }
+
+    /// The recorded checksum of `file`
+    pub fn checksum(&self, file: &str) -> Option<&str> {
+        self.checksums.get(file).map(String::as_str)
+    }
+
+    /// Records the checksum of `content` as the one of `file` and saves the manifest
+    pub fn record(&mut self, file: &str, content: &str) -> Result<(), InputError> {
+        self.checksums
+            .insert(file.to_string(), sha256(content.as_bytes()));
+        self.save()
+    }
+
+    /// Forgets the checksum of `file` and saves the manifest
+    pub fn remove(&mut self, file: &str) -> Result<(), InputError> {
+        if self.checksums.remove(file).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), InputError> {
+        let content: String = self
+            .checksums
+            .iter()
+            .map(|(file, checksum)| format!("{}  {}\n", checksum, file))
+            .collect();
+        write_atomic(&self.path, &content)
+    }
+}
+
+/// The state of a cached input compared to the manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    /// The input matches the recorded checksum
+    Valid,
+    /// The input differs from what was downloaded
+    Modified,
+    /// The input was cached before checksums were recorded and doesn't end with a newline
+    /// like every puzzle input does, so it was probably cut off
+    Truncated,
+    /// The input was cached before checksums were recorded and looks fine
+    Unrecorded,
+}
+
+impl CacheStatus {
+    /// Whether the input should be downloaded again
+    pub fn is_broken(self) -> bool {
+        matches!(self, CacheStatus::Modified | CacheStatus::Truncated)
+    }
+}
+
+impl fmt::Display for CacheStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheStatus::Valid => write!(f, "ok"),
+            CacheStatus::Modified => write!(f, "modified since it was downloaded"),
+            CacheStatus::Truncated => write!(f, "probably truncated"),
+            CacheStatus::Unrecorded => write!(f, "no checksum recorded"),
+        }
+    }
+}
+
+/// Checks every cached input of `profile` in `year` against the manifest. Returns the day and
+/// status of every input, sorted by day.
+pub fn verify_inputs(
+    year: u16,
+    profile: Option<&str>,
+) -> Result<Vec<(u8, CacheStatus)>, InputError> {
+    let dir = input::inputs_dir(year, profile);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let manifest = Manifest::load(&dir)?;
+    let mut statuses = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(io_error(&dir))?.path();
+        let Some(day) = path
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_suffix(".txt")?.parse().ok())
+        else {
+            continue;
+        };
+        let content = fs::read(&path).map_err(io_error(&path))?;
+        let file = format!("{}.txt", day);
+        let status = match manifest.checksum(&file) {
+            Some(checksum) if checksum == sha256(&content) => CacheStatus::Valid,
+            Some(_) => CacheStatus::Modified,
+            None if !content.ends_with(b"\n") => CacheStatus::Truncated,
+            None => CacheStatus::Unrecorded,
+        };
+        statuses.push((day, status));
+    }
+    statuses.sort_by_key(|(day, _)| *day);
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
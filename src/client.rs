@@ -0,0 +1,157 @@
+//! The HTTP client every request to adventofcode.com goes through. It identifies itself with a
+//! User-Agent, keeps a minimum interval between requests across processes, retries transient
+//! failures and applies timeouts.
+
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use std::{
+    fs::{self, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    sync::OnceLock,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    config,
+    input::{io_error, InputError},
+};
+
+const REPOSITORY: &str = "github.com/LarsBollmann/AdventOfCode24";
+
+/// File in the data directory holding the time of the last request, shared by all processes
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const RETRIES: u32 = 3;
+
+/// The User-Agent sent with every request, `AOC_USER_AGENT` or the repository plus the
+/// contact from `AOC_CONTACT`
+pub fn user_agent() -> String {
+    if let Some(user_agent) = config::var("AOC_USER_AGENT") {
+        return user_agent;
+    }
+    match config::var("AOC_CONTACT") {
+        Some(contact) => format!("{} by {}", REPOSITORY, contact),
+        None => REPOSITORY.to_string(),
+    }
+}
+
+fn seconds(key: &'static str, default: Duration) -> Result<Duration, InputError> {
+    match config::var(key) {
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .ok_or(InputError::InvalidConfig { key, value }),
+        None => Ok(default),
+    }
+}
+
+fn client() -> Result<&'static Client, InputError> {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = Client::builder()
+        .user_agent(user_agent())
+        .timeout(seconds("AOC_TIMEOUT", DEFAULT_TIMEOUT)?)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Waits until at least `AOC_MIN_INTERVAL` seconds passed since the last request of any
+/// process, then records the current time as the last request
+fn throttle() -> Result<(), InputError> {
+    let min_interval = seconds("AOC_MIN_INTERVAL", DEFAULT_MIN_INTERVAL)?;
+    if min_interval.is_zero() {
+        return Ok(());
+    }
+
+    let dir = config::data_dir();
+    fs::create_dir_all(&dir).map_err(io_error(&dir))?;
+    let path = &dir.join(LAST_REQUEST_FILE);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(io_error(path))?;
+    // Held until the file is dropped, so other processes wait for their turn
+    file.lock().map_err(io_error(path))?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content).map_err(io_error(path))?;
+    if let Ok(last_request) = content.trim().parse() {
+        let next_request = Duration::from_millis(last_request) + min_interval;
+        if let Some(wait) = next_request.checked_sub(now()) {
+            thread::sleep(wait.min(min_interval));
+        }
+    }
+
+    file.set_len(0).map_err(io_error(path))?;
+    file.seek(SeekFrom::Start(0)).map_err(io_error(path))?;
+    write!(file, "{}", now().as_millis()).map_err(io_error(path))
+}
+
+/// Sends the request built by `build`, retrying with exponential backoff. Connection errors
+/// are always retried, timeouts and server errors only if `idempotent` is set, since a
+/// request that reached the server must not be sent twice otherwise.
+fn send(
+    build: impl Fn(&Client) -> RequestBuilder,
+    idempotent: bool,
+) -> Result<Response, InputError> {
+    let client = client()?;
+    let mut backoff = Duration::from_secs(1);
+    let mut attempt = 0;
+    loop {
+        throttle()?;
+        let result = build(client).send();
+        let transient = match &result {
+            Ok(response) => idempotent && response.status().is_server_error(),
+            Err(err) => err.is_connect() || (idempotent && err.is_timeout()),
+        };
+        if !transient || attempt == RETRIES {
+            return Ok(result?);
+        }
+        attempt += 1;
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+fn with_session(request: RequestBuilder, session: Option<&str>) -> RequestBuilder {
+    match session {
+        Some(session) => request.header("Cookie", format!("session={}", session)),
+        None => request,
+    }
+}
+
+/// Sends a GET request for `path`, e.g. `/2024/day/1/input`
+pub fn get(path: &str, session: Option<&str>) -> Result<Response, InputError> {
+    let url = format!("{}{}", config::base_url(), path);
+    send(|client| with_session(client.get(&url), session), true)
+}
+
+/// Sends a POST request with a form body for `path`, e.g. `/2024/day/1/answer`
+pub fn post_form(
+    path: &str,
+    session: Option<&str>,
+    form: &[(&str, &str)],
+) -> Result<Response, InputError> {
+    let url = format!("{}{}", config::base_url(), path);
+    send(
+        |client| with_session(client.post(&url), session).form(form),
+        false,
+    )
+}
@@ -0,0 +1,301 @@
+use dotenvy::dotenv;
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    sync::Once,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::input::{io_error, InputError};
+
+/// The event year used when `AOC_YEAR` is not set
+pub const DEFAULT_YEAR: u16 = 2024;
+
+/// The origin used when `AOC_BASE_URL` is not set
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Loads the `.env` file into the environment. Only the first call has an effect.
+pub fn load() {
+    static LOAD: Once = Once::new();
+    LOAD.call_once(|| {
+        dotenv().ok();
+    });
+}
+
+/// Reads a setting from the environment or the `.env` file
+pub fn var(key: &str) -> Option<String> {
+    load();
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// The event year to work on, configured through `AOC_YEAR`
+pub fn year() -> Result<u16, InputError> {
+    match var("AOC_YEAR") {
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|year| *year >= 2015)
+            .ok_or(InputError::InvalidConfig {
+                key: "AOC_YEAR",
+                value,
+            }),
+        None => Ok(DEFAULT_YEAR),
+    }
+}
+
+/// The profile to work with, configured through `AOC_PROFILE` or `--profile`. Without one,
+/// the default profile is used.
+pub fn profile() -> Result<Option<String>, InputError> {
+    match var("AOC_PROFILE") {
+        Some(name) if is_profile_name(&name) => Ok(Some(name)),
+        Some(value) => Err(InputError::InvalidConfig {
+            key: "AOC_PROFILE",
+            value,
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Profile names end up in paths, so only letters, digits, `-` and `_` are allowed
+pub fn is_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The directory `dir` holds the files of `profile` in. The default profile uses `dir` itself.
+pub fn profile_dir(dir: PathBuf, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => dir.join(profile),
+        None => dir,
+    }
+}
+
+/// Every profile of `year`: the default one, those with a session in the user config and
+/// those with cached inputs
+pub fn profiles(year: u16) -> Result<Vec<Option<String>>, InputError> {
+    let mut profiles: Vec<_> = UserConfig::load()?.profiles.into_keys().collect();
+    if let Ok(entries) = fs::read_dir(year_dir(year).join("inputs")) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_dir() && is_profile_name(&name) {
+                profiles.push(name);
+            }
+        }
+    }
+    profiles.sort();
+    profiles.dedup();
+    Ok([None]
+        .into_iter()
+        .chain(profiles.into_iter().map(Some))
+        .collect())
+}
+
+/// The session cookie used to authenticate against adventofcode.com. It is read from
+/// `AOC_SESSION` (`AOC_SESSION_{PROFILE}` for named profiles), or else from the user config.
+pub fn session() -> Result<String, InputError> {
+    session_for(profile()?.as_deref())
+}
+
+/// The session cookie of `profile`, see [`session`]
+pub fn session_for(profile: Option<&str>) -> Result<String, InputError> {
+    let key = match profile {
+        Some(profile) => format!("AOC_SESSION_{}", profile.to_uppercase().replace('-', "_")),
+        None => "AOC_SESSION".to_string(),
+    };
+    if let Some(session) = var(&key) {
+        return Ok(session);
+    }
+    UserConfig::load()?
+        .session(profile)
+        .map(|stored| stored.cookie.clone())
+        .ok_or(InputError::MissingSession)
+}
+
+/// A session cookie stored in the user config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredSession {
+    pub cookie: String,
+    /// When the session was stored, in seconds since the Unix epoch
+    pub set_at: Option<u64>,
+}
+
+impl StoredSession {
+    fn parse(table: &toml::Table) -> Result<Option<StoredSession>, String> {
+        let cookie = match table.get("session") {
+            Some(toml::Value::String(cookie)) => cookie.clone(),
+            Some(_) => return Err("session must be a string".to_string()),
+            None => return Ok(None),
+        };
+        let set_at = match table.get("session_set_at") {
+            Some(toml::Value::Integer(time)) => Some(*time as u64),
+            Some(_) => return Err("session_set_at must be an integer".to_string()),
+            None => None,
+        };
+        Ok(Some(StoredSession { cookie, set_at }))
+    }
+
+    fn write(&self, table: &mut toml::Table) {
+        table.insert(
+            "session".to_string(),
+            toml::Value::String(self.cookie.clone()),
+        );
+        if let Some(time) = self.set_at {
+            table.insert(
+                "session_set_at".to_string(),
+                toml::Value::Integer(time as i64),
+            );
+        }
+    }
+}
+
+/// Settings stored outside of the repository, in `~/.config/aoc24/config.toml` or the file
+/// set in `AOC_CONFIG`. It holds secrets, so it is only readable by the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserConfig {
+    /// The session of the default profile
+    pub session: Option<StoredSession>,
+    /// The sessions of named profiles, stored in `[profiles.{name}]` tables
+    pub profiles: BTreeMap<String, StoredSession>,
+}
+
+impl UserConfig {
+    /// The file the user config is stored in, if a home directory is known
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = var("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let dir = var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| Some(PathBuf::from(var("HOME")?).join(".config")))?;
+        Some(dir.join("aoc24").join("config.toml"))
+    }
+
+    /// Loads the user config. A missing file is an empty config.
+    pub fn load() -> Result<UserConfig, InputError> {
+        let Some(path) = UserConfig::path() else {
+            return Ok(Default::default());
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+            Err(err) => return Err(io_error(path)(err)),
+        };
+        let invalid = |reason: String| InputError::InvalidFile {
+            path: path.clone(),
+            reason,
+        };
+        let table: toml::Table = content
+            .parse()
+            .map_err(|err: toml::de::Error| invalid(err.message().to_string()))?;
+
+        let session = StoredSession::parse(&table).map_err(invalid)?;
+        let mut profiles = BTreeMap::new();
+        match table.get("profiles") {
+            Some(toml::Value::Table(tables)) => {
+                for (name, table) in tables {
+                    let stored = match table {
+                        toml::Value::Table(table) if is_profile_name(name) => {
+                            StoredSession::parse(table).map_err(invalid)?
+                        }
+                        _ => return Err(invalid(format!("invalid profile {}", name))),
+                    };
+                    profiles.extend(stored.map(|stored| (name.clone(), stored)));
+                }
+            }
+            Some(_) => return Err(invalid("profiles must be a table".to_string())),
+            None => {}
+        }
+        Ok(UserConfig { session, profiles })
+    }
+
+    /// Writes the user config, readable only by the user. Returns the file it was written to.
+    pub fn save(&self) -> Result<PathBuf, InputError> {
+        let path = UserConfig::path().ok_or(InputError::InvalidConfig {
+            key: "AOC_CONFIG",
+            value: String::new(),
+        })?;
+        let mut table = toml::Table::new();
+        if let Some(session) = &self.session {
+            session.write(&mut table);
+        }
+        let mut profiles = toml::Table::new();
+        for (name, session) in &self.profiles {
+            let mut profile = toml::Table::new();
+            session.write(&mut profile);
+            profiles.insert(name.clone(), toml::Value::Table(profile));
+        }
+        if !profiles.is_empty() {
+            table.insert("profiles".to_string(), toml::Value::Table(profiles));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error(dir))?;
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // The mode only applies to new files
+            if path.exists() {
+                fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+                    .map_err(io_error(&path))?;
+            }
+        }
+        let mut file = options.open(&path).map_err(io_error(&path))?;
+        file.write_all(table.to_string().as_bytes())
+            .map_err(io_error(&path))?;
+        Ok(path)
+    }
+
+    /// The session stored for `profile`, `None` being the default profile
+    pub fn session(&self, profile: Option<&str>) -> Option<&StoredSession> {
+        match profile {
+            Some(profile) => self.profiles.get(profile),
+            None => self.session.as_ref(),
+        }
+    }
+
+    /// Stores `cookie` as the session of `profile` together with the current time
+    pub fn set_session(&mut self, profile: Option<&str>, cookie: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let stored = StoredSession {
+            cookie: cookie.to_string(),
+            set_at: Some(now.as_secs()),
+        };
+        match profile {
+            Some(profile) => {
+                self.profiles.insert(profile.to_string(), stored);
+            }
+            None => self.session = Some(stored),
+        }
+    }
+}
+
+/// The origin all requests go to, configured through `AOC_BASE_URL`, e.g. to talk to
+/// `aoc-mock` instead of adventofcode.com
+pub fn base_url() -> String {
+    var("AOC_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+/// The directory holding inputs, examples and everything else that is cached, configured
+/// through `AOC_DATA_DIR`
+pub fn data_dir() -> PathBuf {
+    var("AOC_DATA_DIR").map_or_else(|| PathBuf::from("data"), PathBuf::from)
+}
+
+/// The directory holding everything cached for `year`
+pub fn year_dir(year: u16) -> PathBuf {
+    data_dir().join(year.to_string())
+}
@@ -0,0 +1,13 @@
+//! Inputs and examples compiled into the binary. With the `embed-inputs` feature, the build
+//! script embeds every input and example that is in the data directory at build time, so the
+//! binaries work without the data directory and without a session. Otherwise both are empty.
+
+include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+/// The embedded example `name` of `day` in `year` as input and expected answers file content
+pub fn example(year: u16, day: u8, name: &str) -> Option<(&'static str, &'static str)> {
+    EXAMPLES
+        .iter()
+        .find(|example| example.0 == year && example.1 == day && example.2 == name)
+        .map(|example| (example.3, example.4))
+}
@@ -0,0 +1,245 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cache, config, embedded,
+    input::{self, io_error, InputError},
+    Part,
+};
+
+/// Name of the example used when a day has only one
+pub const DEFAULT_EXAMPLE: &str = "default";
+
+/// Directory holding the examples of `year`
+pub fn examples_dir(year: u16) -> PathBuf {
+    config::year_dir(year).join("examples")
+}
+
+/// Directory holding the examples of `day` in `year`, one `{name}.txt` file per example
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    examples_dir(year).join(day.to_string())
+}
+
+/// The file of the example `name` of `day` in `year`
+pub fn example_path(year: u16, day: u8, name: &str) -> PathBuf {
+    day_dir(year, day).join(format!("{}.txt", name))
+}
+
+/// The answers an example is expected to produce. They are stored next to the example, e.g.
+/// `default.answers.toml` for `default.txt`, with optional `part_one` and `part_two` keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl ExpectedAnswers {
+    /// The file holding the expected answers of the example at `example_path`
+    pub fn path(example_path: &Path) -> PathBuf {
+        example_path.with_extension("answers.toml")
+    }
+
+    /// Loads the expected answers of the example at `example_path`. A missing file means
+    /// that no answers are known.
+    pub fn load(example_path: &Path) -> Result<ExpectedAnswers, InputError> {
+        let path = ExpectedAnswers::path(example_path);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+            Err(err) => return Err(io_error(path)(err)),
+        };
+        ExpectedAnswers::parse(&content, &path)
+    }
+
+    /// Parses the content of the expected answers file at `path`
+    pub fn parse(content: &str, path: &Path) -> Result<ExpectedAnswers, InputError> {
+        let table: toml::Table =
+            content
+                .parse()
+                .map_err(|err: toml::de::Error| InputError::InvalidFile {
+                    path: path.to_path_buf(),
+                    reason: err.message().to_string(),
+                })?;
+        let answer = |key: &str| match table.get(key) {
+            Some(toml::Value::String(answer)) => Ok(Some(answer.clone())),
+            Some(toml::Value::Integer(answer)) => Ok(Some(answer.to_string())),
+            Some(_) => Err(InputError::InvalidFile {
+                path: path.to_path_buf(),
+                reason: format!("{} must be a string or an integer", key),
+            }),
+            None => Ok(None),
+        };
+        Ok(ExpectedAnswers {
+            part_one: answer("part_one")?,
+            part_two: answer("part_two")?,
+        })
+    }
+
+    /// Stores the answers next to the example at `example_path`
+    pub fn save(&self, example_path: &Path) -> Result<(), InputError> {
+        let path = ExpectedAnswers::path(example_path);
+        let mut table = toml::Table::new();
+        for (key, answer) in [("part_one", &self.part_one), ("part_two", &self.part_two)] {
+            if let Some(answer) = answer {
+                table.insert(key.to_string(), toml::Value::String(answer.clone()));
+            }
+        }
+        cache::write_atomic(&path, &table.to_string())
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: String) {
+        match part {
+            Part::One => self.part_one = Some(answer),
+            Part::Two => self.part_two = Some(answer),
+        }
+    }
+}
+
+/// An example input together with the answers it should produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: ExpectedAnswers,
+}
+
+/// Names of all non-empty examples of `day` in `year`, including the embedded ones, with
+/// [`DEFAULT_EXAMPLE`] first and the others sorted by name
+pub fn list_examples(year: u16, day: u8) -> Result<Vec<String>, InputError> {
+    let mut names: Vec<String> = embedded::EXAMPLES
+        .iter()
+        .filter(|example| example.0 == year && example.1 == day)
+        .map(|example| example.2.to_string())
+        .collect();
+    let dir = day_dir(year, day);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            names.sort_by_key(|name| (name != DEFAULT_EXAMPLE, name.clone()));
+            return Ok(names);
+        }
+        Err(err) => return Err(io_error(dir)(err)),
+    };
+    for entry in entries {
+        let entry = entry.map_err(io_error(&dir))?;
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        // Skip the empty placeholders created for pasting an example into
+        if entry.metadata().is_ok_and(|metadata| metadata.len() == 0) {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort_by_key(|name| (name != DEFAULT_EXAMPLE, name.clone()));
+    names.dedup();
+    Ok(names)
+}
+
+/// Loads the example `name` of `day` in `year` with its expected answers, see
+/// [`input::normalize`]. Falls back to the embedded example if there is no such file.
+pub fn load_example(year: u16, day: u8, name: &str) -> Result<Example, InputError> {
+    let path = example_path(year, day, name);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            match embedded::example(year, day, name) {
+                Some((input, answers)) => {
+                    return Ok(Example {
+                        name: name.to_string(),
+                        input: input::normalize(input),
+                        answers: ExpectedAnswers::parse(answers, &ExpectedAnswers::path(&path))?,
+                    })
+                }
+                None => String::new(),
+            }
+        }
+        Err(err) => return Err(io_error(path)(err)),
+    };
+    if input.is_empty() {
+        return Err(InputError::MissingExample { day, path });
+    }
+    Ok(Example {
+        name: name.to_string(),
+        input: input::normalize(&input),
+        answers: ExpectedAnswers::load(&path)?,
+    })
+}
+
+/// Loads every example of `day` for the configured year, see [`list_examples`]
+pub fn get_examples(day: u8) -> Result<Vec<Example>, InputError> {
+    let year = config::year()?;
+    list_examples(year, day)?
+        .iter()
+        .map(|name| load_example(year, day, name))
+        .collect()
+}
+
+/// Returns the example of `day` for the configured year, see [`get_example_for`]
+pub fn get_example(day: u8) -> Result<String, InputError> {
+    get_example_for(config::year()?, day)
+}
+
+/// Returns the input of the first example of `day` in `year`, see [`list_examples`]. Creates
+/// an empty example file to paste into if there is none yet.
+pub fn get_example_for(year: u16, day: u8) -> Result<String, InputError> {
+    if let Some(name) = list_examples(year, day)?.first() {
+        return Ok(load_example(year, day, name)?.input);
+    }
+    let path = example_path(year, day, DEFAULT_EXAMPLE);
+    if !path.exists() {
+        let dir = day_dir(year, day);
+        fs::create_dir_all(&dir).map_err(io_error(dir))?;
+        fs::write(&path, "").map_err(io_error(&path))?;
+    }
+    Err(InputError::MissingExample { day, path })
+}
+
+/// Moves examples stored as `{day}.txt`, `{day}.answers.toml` and `{day}.part-two.txt` into
+/// the per-day directories as `default` and `part-two`. Returns the files that were moved.
+pub fn migrate_single_examples(year: u16) -> Result<Vec<PathBuf>, InputError> {
+    let dir = examples_dir(year);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut moved = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(io_error(&dir))?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some((day, rest)) = file_name.split_once('.') else {
+            continue;
+        };
+        let Ok(day) = day.parse::<u8>() else {
+            continue;
+        };
+        let target = match rest {
+            "txt" | "answers.toml" => format!("{}.{}", DEFAULT_EXAMPLE, rest),
+            "part-two.txt" => "part-two.txt".to_string(),
+            "part-two.answers.toml" => "part-two.answers.toml".to_string(),
+            _ => continue,
+        };
+        let target = day_dir(year, day).join(target);
+        if target.exists() {
+            continue;
+        }
+        fs::create_dir_all(day_dir(year, day)).map_err(io_error(day_dir(year, day)))?;
+        fs::rename(&path, &target).map_err(io_error(&path))?;
+        moved.push(target);
+    }
+    moved.sort();
+    Ok(moved)
+}
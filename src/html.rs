@@ -0,0 +1,161 @@
+//! Just enough HTML handling for the pages of adventofcode.com
+
+/// A piece of an HTML document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An opening tag with its name and the raw attribute string
+    Open { name: &'a str, attributes: &'a str },
+    /// A closing tag with its name
+    Close(&'a str),
+    /// Text between tags, with entities still encoded
+    Text(&'a str),
+}
+
+/// Splits `html` into tags and text. Comments, doctypes, and the content of `script` and
+/// `style` elements are skipped.
+pub fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim()));
+        } else if !tag.starts_with('!') {
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Open { name, attributes });
+            if name == "script" || name == "style" {
+                let close = format!("</{}", name);
+                rest = rest.find(&close).map_or("", |end| &rest[end..]);
+            }
+        }
+    }
+    tokens
+}
+
+/// Returns the value of the attribute `name` in the raw attribute string of a tag
+pub fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(position) = rest.find(name) {
+        let after = rest[position + name.len()..].trim_start();
+        let boundary = position == 0 || rest[..position].ends_with(char::is_whitespace);
+        if let (true, Some(value)) = (boundary, after.strip_prefix('=')) {
+            let value = value.trim_start();
+            let quote = value.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let value = &value[1..];
+                return value.find(quote).map(|end| &value[..end]);
+            }
+            return Some(value.split_whitespace().next().unwrap_or(""));
+        }
+        rest = &rest[position + name.len()..];
+    }
+    None
+}
+
+/// Replaces the character references adventofcode.com uses with the characters they stand for
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let character = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                .and_then(char::from_u32),
+        };
+        match character {
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize(
+            "<!DOCTYPE html><!-- x --><article class=\"day-desc\"><p>a &lt; b</p><br/></article>",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Open {
+                    name: "article",
+                    attributes: "class=\"day-desc\""
+                },
+                Token::Open {
+                    name: "p",
+                    attributes: ""
+                },
+                Token::Text("a &lt; b"),
+                Token::Close("p"),
+                Token::Open {
+                    name: "br",
+                    attributes: ""
+                },
+                Token::Close("article"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        let attributes = "href=\"/2024/day/1/input\" target=_blank data-x='y'";
+        assert_eq!(attribute(attributes, "href"), Some("/2024/day/1/input"));
+        assert_eq!(attribute(attributes, "target"), Some("_blank"));
+        assert_eq!(attribute(attributes, "x"), None);
+        assert_eq!(attribute(attributes, "data-x"), Some("y"));
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp;&#39;&#x41;"), "a <b> &'A");
+        assert_eq!(decode_entities("AT&T; &unknown;"), "AT&T; &unknown;");
+    }
+}
@@ -1,53 +1,427 @@
-use dotenvy::dotenv;
-use reqwest::blocking::Client;
-use core::panic;
-use std::{env, fs};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::StatusCode;
+use std::{error::Error, fmt, fs, io, path::PathBuf, thread, time::Duration};
 
-pub fn get_input(day: u8) -> String {
-    let input_file = format!("data/inputs/{}.txt", day);
-    if let Ok(input) = fs::read_to_string(input_file) {
-        return input;
+pub use crate::example::{get_example, get_example_for};
+use crate::{
+    cache::{self, Manifest},
+    client, config,
+    example::examples_dir,
+    ledger::Rejection,
+    schedule,
+    source::SourceChain,
+};
+
+#[derive(Debug)]
+pub enum InputError {
+    /// `AOC_SESSION` is neither set in the environment, the `.env` file nor the user config
+    MissingSession,
+    /// The session cookie was rejected by adventofcode.com, usually because it expired
+    Unauthorized,
+    /// The puzzle for the requested day has not been unlocked yet
+    NotUnlocked {
+        year: u16,
+        day: u8,
+        unlocks_in: Option<Duration>,
+    },
+    /// The requested day is not part of the event
+    InvalidDay { year: u16, day: u8 },
+    /// The request failed or adventofcode.com answered with an unexpected status
+    Network(reqwest::Error),
+    /// Reading or writing a file in the `data` directory failed
+    Io { path: PathBuf, source: io::Error },
+    /// The example file does not exist yet or is empty
+    MissingExample { day: u8, path: PathBuf },
+    /// A setting from the environment or `.env` file has an invalid value
+    InvalidConfig { key: &'static str, value: String },
+    /// adventofcode.com answered with something that is not a puzzle input, e.g. an HTML page
+    InvalidInput { reason: &'static str },
+    /// adventofcode.com answered with a page that could not be understood
+    UnexpectedResponse { reason: String },
+    /// The answer was not submitted because the ledger already knows it is wrong
+    AnswerRejected { answer: String, reason: Rejection },
+    /// A file in the `data` directory could not be parsed
+    InvalidFile { path: PathBuf, reason: String },
+    /// None of the configured input sources has the input
+    NoSource {
+        year: u16,
+        day: u8,
+        sources: Vec<&'static str>,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::MissingSession => write!(
+                f,
+                "Could not find AOC_SESSION. Store a valid AoC session cookie with `cargo aoc session set` or set it in the environment or .env file"
+            ),
+            InputError::Unauthorized => write!(
+                f,
+                "The AoC session cookie was rejected. It has probably expired, log in again and run `cargo aoc session set`"
+            ),
+            InputError::NotUnlocked {
+                year,
+                day,
+                unlocks_in: Some(remaining),
+            } => write!(
+                f,
+                "Day {} of {} unlocks in {}",
+                day,
+                year,
+                schedule::format_duration(*remaining)
+            ),
+            InputError::NotUnlocked { year, day, .. } => {
+                write!(f, "Day {} of {} has not been unlocked yet", day, year)
+            }
+            InputError::InvalidDay { year, day } => {
+                write!(f, "Day {} is not part of Advent of Code {}", day, year)
+            }
+            InputError::Network(err) => write!(f, "Error talking to adventofcode.com: {}", err),
+            InputError::Io { path, source } => {
+                write!(f, "Error accessing {}: {}", path.display(), source)
+            }
+            InputError::MissingExample { day, path } => write!(
+                f,
+                "No example input found for day {}. Paste the example input into {} or run `cargo aoc example {}`",
+                day,
+                path.display(),
+                day
+            ),
+            InputError::InvalidConfig { key, value } => {
+                write!(f, "Invalid value for {}: {:?}", key, value)
+            }
+            InputError::InvalidInput { reason } => {
+                write!(f, "Received an invalid puzzle input: {}", reason)
+            }
+            InputError::UnexpectedResponse { reason } => {
+                write!(f, "Unexpected response from adventofcode.com: {}", reason)
+            }
+            InputError::AnswerRejected { answer, reason } => {
+                write!(f, "Not submitting {}: {}", answer, reason)
+            }
+            InputError::InvalidFile { path, reason } => {
+                write!(f, "Could not parse {}: {}", path.display(), reason)
+            }
+            InputError::NoSource { year, day, sources } => write!(
+                f,
+                "None of the input sources ({}) has the input of day {} of {}",
+                sources.join(", "),
+                day,
+                year
+            ),
+        }
     }
+}
 
-    dotenv().ok();
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Network(err) => Some(err),
+            InputError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
-    let client = Client::new();
+impl From<reqwest::Error> for InputError {
+    fn from(err: reqwest::Error) -> Self {
+        InputError::Network(err)
+    }
+}
 
-    let input = client
-        .get(format!("https://adventofcode.com/2024/day/{}/input", day))
-        .header(
-            "Cookie",
-            format!(
-                "session={}",
-                env::var("AOC_SESSION").expect("Could not find session environment variable")
-            ),
-        )
-        .send()
-        .expect("Error getting input")
-        .text()
-        .expect("Error parsing input");
+pub(crate) fn io_error(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> InputError {
+    let path = path.into();
+    move |source| InputError::Io { path, source }
+}
+
+/// Directory holding the cached inputs of `profile` in `year`
+pub fn inputs_dir(year: u16, profile: Option<&str>) -> PathBuf {
+    config::profile_dir(config::year_dir(year).join("inputs"), profile)
+}
+
+/// Returns the input of `day` for the configured year, see [`get_input_for`]
+pub fn get_input(day: u8) -> Result<String, InputError> {
+    get_input_for(config::year()?, day)
+}
+
+/// What to do with the newlines at the end of an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingNewline {
+    /// Remove them, so the last character is part of the last line
+    Strip,
+    /// Keep exactly one
+    Single,
+}
+
+/// Removes a byte order mark, converts CRLF line endings to LF and strips trailing newlines,
+/// so solutions see the same input no matter where it came from
+pub fn normalize(input: &str) -> String {
+    normalize_with(input, TrailingNewline::Strip)
+}
+
+/// Like [`normalize`], with the given handling of trailing newlines
+pub fn normalize_with(input: &str, trailing: TrailingNewline) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let input = input.replace("\r\n", "\n");
+    let trimmed = input.trim_end_matches('\n');
+    match trailing {
+        TrailingNewline::Strip => trimmed.to_string(),
+        TrailingNewline::Single => format!("{}\n", trimmed),
+    }
+}
+
+/// Checks that `input` looks like the puzzle input of `day` in `year` and not like one of the
+/// error pages adventofcode.com serves with a success status
+pub fn validate_input(year: u16, day: u8, input: &str) -> Result<(), InputError> {
+    if input.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(InputError::NotUnlocked {
+            year,
+            day,
+            unlocks_in: schedule::time_until_unlock(year, day),
+        });
+    }
+    if input.contains("Puzzle inputs differ by user") {
+        return Err(InputError::Unauthorized);
+    }
+    let start = input.trim_start().to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err(InputError::InvalidInput {
+            reason: "got an HTML page",
+        });
+    }
+    if input.trim().is_empty() {
+        return Err(InputError::InvalidInput {
+            reason: "the input is empty",
+        });
+    }
+    Ok(())
+}
+
+/// The file the input of `day` in `year` is cached in for `profile`
+pub fn input_path(year: u16, profile: Option<&str>, day: u8) -> PathBuf {
+    inputs_dir(year, profile).join(format!("{}.txt", day))
+}
 
-    fs::create_dir_all("data/inputs").expect("Error creating inputs directory");
-    fs::write(format!("data/inputs/{}.txt", day), &input).expect("Error writing input");
+/// Returns the cached input of `day` in `year` for the configured profile, if there is one. A
+/// cached file that fails [`validate_input`] is removed.
+pub fn read_cached_input(year: u16, day: u8) -> Result<Option<String>, InputError> {
+    let input_file = input_path(year, config::profile()?.as_deref(), day);
+    let Ok(input) = fs::read_to_string(&input_file) else {
+        return Ok(None);
+    };
+    if validate_input(year, day, &input).is_ok() {
+        return Ok(Some(input));
+    }
+    fs::remove_file(&input_file).map_err(io_error(&input_file))?;
+    Ok(None)
+}
+
+/// Downloads the input of `day` in `year` for the configured profile from adventofcode.com and
+/// caches it
+pub fn download_input(year: u16, day: u8) -> Result<String, InputError> {
+    schedule::check_unlocked(year, day)?;
+    let profile = config::profile()?;
+    let session = config::session()?;
+
+    let response = client::get(&format!("/{}/day/{}/input", year, day), Some(&session))?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => {
+            return Err(InputError::NotUnlocked {
+                year,
+                day,
+                unlocks_in: schedule::time_until_unlock(year, day),
+            })
+        }
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            return Err(InputError::Unauthorized)
+        }
+        _ => {}
+    }
+
+    let input = response.error_for_status()?.text()?;
+    validate_input(year, day, &input)?;
+
+    let dir = inputs_dir(year, profile.as_deref());
+    cache::write_atomic(&input_path(year, profile.as_deref(), day), &input)?;
+    Manifest::load(&dir)?.record(&format!("{}.txt", day), &input)?;
+
+    Ok(input)
+}
+
+/// Returns the input of `day` in `year` from the sources in `AOC_INPUT_SOURCES` that load the
+/// own puzzle input, see [`SourceChain::own_from_config`]. By default that is the embedded or
+/// cached input, or else the one downloaded from adventofcode.com. Cached files that fail
+/// [`validate_input`] are removed and downloaded again.
+pub fn get_input_for(year: u16, day: u8) -> Result<String, InputError> {
+    Ok(SourceChain::own_from_config()?.load(year, day)?.0)
+}
+
+/// Like [`get_input_for`], but waits with a countdown if the puzzle is not unlocked yet and
+/// downloads the input as soon as it is
+pub fn get_input_when_unlocked(year: u16, day: u8) -> Result<String, InputError> {
+    schedule::wait_for_unlock(year, day)?;
+    // The server clock may lag slightly behind ours, so retry for a few seconds
+    for _ in 0..5 {
+        match get_input_for(year, day) {
+            Err(InputError::NotUnlocked { .. }) => thread::sleep(Duration::from_secs(2)),
+            result => return result,
+        }
+    }
+    get_input_for(year, day)
+}
 
-    input
+/// The days [`prefetch_inputs`] downloaded, found in the cache or skipped because they are
+/// still locked
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefetchSummary {
+    pub fetched: Vec<u8>,
+    pub cached: Vec<u8>,
+    pub locked: Vec<u8>,
 }
 
-pub fn get_example(day: u8) -> String {
-    let input_file = format!("data/examples/{}.txt", day);
-    let input = {
-        if let Ok(input) = fs::read_to_string(input_file) {
-            input
+/// Downloads the input of every unlocked day of `year` that is not cached yet for the
+/// configured profile, showing a progress bar
+pub fn prefetch_inputs(year: u16) -> Result<PrefetchSummary, InputError> {
+    let last_day = schedule::last_day(year);
+    let progress = ProgressBar::new(last_day as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{prefix} {wide_bar} {pos}/{len} {msg}")
+            .expect("Invalid progress bar template"),
+    );
+    progress.set_prefix(format!("Prefetching {}", year));
+
+    let mut summary = PrefetchSummary::default();
+    for day in 1..=last_day {
+        progress.set_message(format!("day {}", day));
+        if read_cached_input(year, day)?.is_some() {
+            summary.cached.push(day);
+        } else if schedule::time_until_unlock(year, day).is_some() {
+            summary.locked.push(day);
         } else {
-            fs::create_dir_all("data/examples").expect("Error creating examples directory");
-            fs::write(format!("data/examples/{}.txt", day), "").expect("Error writing input");
-            String::from("")
+            match download_input(year, day) {
+                Ok(_) => summary.fetched.push(day),
+                Err(InputError::NotUnlocked { .. }) => summary.locked.push(day),
+                Err(err) => {
+                    progress.abandon();
+                    return Err(err);
+                }
+            }
         }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    Ok(summary)
+}
+
+/// Runs [`validate_input`] on every cached input of `year` for the configured profile and
+/// removes the files that fail. Returns the removed files together with the reason they were
+/// rejected.
+pub fn remove_invalid_inputs(year: u16) -> Result<Vec<(PathBuf, InputError)>, InputError> {
+    let dir = inputs_dir(year, config::profile()?.as_deref());
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
     };
-    match input.is_empty() {
-        true => {
-            panic!("\n\nERROR: No example input found for day {}. Paste the example input into data/examples/{}.txt", day, day);
+    let mut removed = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(io_error(&dir))?.path();
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let Some(day) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str()?.parse().ok())
+        else {
+            continue;
+        };
+        let input = fs::read_to_string(&path).map_err(io_error(&path))?;
+        if let Err(err) = validate_input(year, day, &input) {
+            fs::remove_file(&path).map_err(io_error(&path))?;
+            Manifest::load(&dir)?.remove(&format!("{}.txt", day))?;
+            removed.push((path, err));
+        }
+    }
+    removed.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(removed)
+}
+
+/// Moves inputs and examples from the flat `data/inputs` and `data/examples` layout used
+/// before multi-year support into `data/{year}/...`. Files that already exist in the new
+/// location are left untouched. Returns the files that were moved.
+pub fn migrate_flat_cache(year: u16) -> Result<Vec<PathBuf>, InputError> {
+    let mut moved = Vec::new();
+    for (from, to) in [
+        (config::data_dir().join("inputs"), inputs_dir(year, None)),
+        (config::data_dir().join("examples"), examples_dir(year)),
+    ] {
+        let Ok(entries) = fs::read_dir(&from) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.map_err(io_error(&from))?.path();
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+            let target = to.join(path.file_name().unwrap());
+            if target.exists() {
+                continue;
+            }
+            fs::create_dir_all(&to).map_err(io_error(&to))?;
+            fs::rename(&path, &target).map_err(io_error(&path))?;
+            moved.push(target);
         }
-        false => input,
+        // Only succeeds if nothing else is left in the old directory
+        fs::remove_dir(&from).ok();
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}1 2\r\n\r\n3 4\r\n\n"), "1 2\n\n3 4");
+        assert_eq!(normalize("2333133121414131402\n"), "2333133121414131402");
+        assert_eq!(
+            normalize_with("1 2\r\n3 4", TrailingNewline::Single),
+            "1 2\n3 4\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_input() {
+        assert!(validate_input(2024, 1, "3   4\n4   3\n").is_ok());
+        assert!(matches!(
+            validate_input(
+                2024,
+                1,
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+            ),
+            Err(InputError::NotUnlocked {
+                year: 2024,
+                day: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            validate_input(
+                2024,
+                1,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InputError::Unauthorized)
+        ));
+        assert!(matches!(
+            validate_input(2024, 1, "<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InputError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            validate_input(2024, 1, "\n"),
+            Err(InputError::InvalidInput { .. })
+        ));
     }
 }
@@ -0,0 +1,245 @@
+//! Private leaderboards, fetched from the JSON API of adventofcode.com. The API asks to be
+//! polled at most every 15 minutes, so fetched leaderboards are cached and only fetched again
+//! once the cached one is older than that.
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    cache, client, config,
+    input::{io_error, InputError},
+    schedule,
+};
+
+/// The minimum time between two requests for the same leaderboard
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// When a member got a star
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+    pub star_index: u64,
+}
+
+/// A member of a private leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub global_score: u32,
+    pub last_star_ts: u64,
+    /// The stars by day and part, both as strings like in the API
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+impl Member {
+    /// The name shown on the leaderboard, which for anonymous users contains their id
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// The star of `part` of `day`, if the member got it
+    pub fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+    }
+}
+
+/// A private leaderboard as returned by `/{year}/leaderboard/private/view/{id}.json`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    /// Parses the JSON returned by the API
+    pub fn parse(json: &str) -> Result<Leaderboard, InputError> {
+        serde_json::from_str(json).map_err(|err| InputError::UnexpectedResponse {
+            reason: format!("the leaderboard is not valid: {}", err),
+        })
+    }
+
+    /// The members ordered like on the website: by local score, ties broken by who got their
+    /// last star first
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|member| (u32::MAX - member.local_score, member.last_star_ts));
+        members
+    }
+}
+
+/// The file caching leaderboard `id` of `year`
+pub fn leaderboard_path(year: u16, id: u64) -> PathBuf {
+    config::year_dir(year)
+        .join("leaderboards")
+        .join(format!("{}.json", id))
+}
+
+/// Returns leaderboard `id` of `year` and how long ago it was fetched. It is only fetched
+/// again if the cached one is older than [`POLL_INTERVAL`].
+pub fn get_leaderboard(year: u16, id: u64) -> Result<(Leaderboard, Duration), InputError> {
+    let path = leaderboard_path(year, id);
+    let age = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default()
+        });
+    if let Some(age) = age.filter(|age| *age < POLL_INTERVAL) {
+        let json = fs::read_to_string(&path).map_err(io_error(&path))?;
+        if let Ok(leaderboard) = Leaderboard::parse(&json) {
+            return Ok((leaderboard, age));
+        }
+    }
+
+    let session = config::session()?;
+    let response = client::get(
+        &format!("/{}/leaderboard/private/view/{}.json", year, id),
+        Some(&session),
+    )?;
+    if response.status() == StatusCode::FORBIDDEN {
+        return Err(InputError::Unauthorized);
+    }
+    let json = response.error_for_status()?.text()?;
+    // Without a valid session the API redirects to the login page
+    if json.trim_start().starts_with('<') {
+        return Err(InputError::Unauthorized);
+    }
+    let leaderboard = Leaderboard::parse(&json)?;
+    cache::write_atomic(&path, &json)?;
+    Ok((leaderboard, Duration::ZERO))
+}
+
+/// Formats the time between the unlock of `day` and `timestamp`, see
+/// [`schedule::format_clock`]
+fn format_star_time(year: u16, day: u8, timestamp: u64) -> String {
+    let unlock = schedule::unlock_time(year, day);
+    let star = SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp);
+    schedule::format_clock(star.duration_since(unlock).unwrap_or_default())
+}
+
+/// Renders the leaderboard as text: one line per member with their stars per day (`*` for
+/// both parts, `+` for part one only) and scores, followed by the star times of every day
+pub fn render(leaderboard: &Leaderboard, year: u16) -> String {
+    let ranking = leaderboard.ranking();
+    let last_day = schedule::last_day(year);
+    let names: Vec<_> = ranking.iter().map(|member| member.display_name()).collect();
+    let name_width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    let rank_width = ranking.len().to_string().len() + 1;
+    let indent = rank_width + 1 + name_width + 2;
+
+    let mut text = String::new();
+    let tens: String = (1..=last_day)
+        .map(|day| match day / 10 {
+            0 => ' ',
+            tens => char::from(b'0' + tens),
+        })
+        .collect();
+    let ones: String = (1..=last_day)
+        .map(|day| char::from(b'0' + day % 10))
+        .collect();
+    writeln!(text, "{:indent$}{}", "", tens.trim_end()).unwrap();
+    writeln!(
+        text,
+        "{:indent$}{}  {:>5}  {:>6}",
+        "", ones, "Local", "Global"
+    )
+    .unwrap();
+    for (rank, (member, name)) in ranking.iter().zip(&names).enumerate() {
+        let stars: String = (1..=last_day)
+            .map(|day| match (member.star(day, 1), member.star(day, 2)) {
+                (Some(_), Some(_)) => '*',
+                (Some(_), None) => '+',
+                _ => '.',
+            })
+            .collect();
+        writeln!(
+            text,
+            "{:>rank_width$} {:name_width$}  {}  {:>5}  {:>6}",
+            format!("{})", rank + 1),
+            name,
+            stars,
+            member.local_score,
+            member.global_score
+        )
+        .unwrap();
+    }
+
+    for day in 1..=last_day {
+        let mut finishers: Vec<_> = ranking
+            .iter()
+            .zip(&names)
+            .filter_map(|(member, name)| Some((member.star(day, 1)?, member.star(day, 2), name)))
+            .collect();
+        if finishers.is_empty() {
+            continue;
+        }
+        finishers.sort_by_key(|(one, two, _)| {
+            (
+                two.map_or(u64::MAX, |star| star.get_star_ts),
+                one.get_star_ts,
+            )
+        });
+        writeln!(text, "\nDay {}", day).unwrap();
+        for (one, two, name) in finishers {
+            let two = two.map_or("--:--:--".to_string(), |star| {
+                format_star_time(year, day, star.get_star_ts)
+            });
+            writeln!(
+                text,
+                "  {:name_width$}  {:>8}  {:>8}",
+                name,
+                format_star_time(year, day, one.get_star_ts),
+                two
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"event":"2024","owner_id":1,"day1_ts":1733029200,"members":{
+"1":{"id":1,"name":"Owner","stars":2,"local_score":4,"global_score":0,"last_star_ts":1733032800,
+"completion_day_level":{"1":{"1":{"get_star_ts":1733030000,"star_index":10},"2":{"get_star_ts":1733032800,"star_index":20}}}},
+"2":{"id":2,"name":null,"stars":1,"local_score":1,"global_score":0,"last_star_ts":1733040000,
+"completion_day_level":{"1":{"1":{"get_star_ts":1733040000,"star_index":30}}}}}}"#;
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        assert_eq!(
+            render(&leaderboard, 2024),
+            "                                 1111111111222222
+                        1234567890123456789012345  Local  Global
+1) Owner                *........................      4       0
+2) (anonymous user #2)  +........................      1       0
+
+Day 1
+  Owner                00:13:20  01:00:00
+  (anonymous user #2)  03:00:00  --:--:--
+"
+        );
+    }
+}
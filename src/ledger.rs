@@ -0,0 +1,267 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    config,
+    input::{io_error, InputError},
+    submit::SubmitOutcome,
+    Part,
+};
+
+/// Directory holding the answer ledgers of `profile` in `year`
+pub fn ledger_dir(year: u16, profile: Option<&str>) -> PathBuf {
+    config::profile_dir(config::year_dir(year).join("answers"), profile)
+}
+
+/// A submitted answer and how adventofcode.com judged it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub part: Part,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+/// Why an answer is not worth submitting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part was already solved with the contained answer
+    AlreadySolved { accepted: String },
+    /// The same answer was submitted before and was wrong
+    KnownWrong { outcome: SubmitOutcome },
+    /// The answer is at least as high as an answer that was too high
+    TooHigh { bound: String },
+    /// The answer is at most as low as an answer that was too low
+    TooLow { bound: String },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::AlreadySolved { accepted } => {
+                write!(f, "the part was already solved with {}", accepted)
+            }
+            Rejection::KnownWrong { outcome } => {
+                write!(f, "it was submitted before: {}", outcome)
+            }
+            Rejection::TooHigh { bound } => write!(f, "{} was already too high", bound),
+            Rejection::TooLow { bound } => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+fn outcome_name(outcome: SubmitOutcome) -> Option<&'static str> {
+    match outcome {
+        SubmitOutcome::Correct => Some("correct"),
+        SubmitOutcome::TooHigh => Some("too-high"),
+        SubmitOutcome::TooLow => Some("too-low"),
+        SubmitOutcome::Wrong => Some("wrong"),
+        // These don't say anything about the answer itself
+        SubmitOutcome::RateLimited(_) | SubmitOutcome::AlreadySolved => None,
+    }
+}
+
+fn parse_outcome_name(name: &str) -> Option<SubmitOutcome> {
+    match name {
+        "correct" => Some(SubmitOutcome::Correct),
+        "too-high" => Some(SubmitOutcome::TooHigh),
+        "too-low" => Some(SubmitOutcome::TooLow),
+        "wrong" => Some(SubmitOutcome::Wrong),
+        _ => None,
+    }
+}
+
+/// Every answer submitted for one day, stored in `data/{year}/answers/{day}.txt` with one
+/// `{part}\t{outcome}\t{answer}` line per submission. Named profiles keep their ledgers in
+/// `answers/{profile}/`.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Loads the ledger of `day` in `year` for the configured profile, see [`Ledger::load_for`]
+    pub fn load(year: u16, day: u8) -> Result<Ledger, InputError> {
+        Ledger::load_for(year, day, config::profile()?.as_deref())
+    }
+
+    /// Loads the ledger of `day` in `year` for `profile`. A missing file is an empty ledger.
+    pub fn load_for(year: u16, day: u8, profile: Option<&str>) -> Result<Ledger, InputError> {
+        let path = ledger_dir(year, profile).join(format!("{}.txt", day));
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(io_error(path)(err)),
+        };
+        let entries = content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let part = Part::from_level(fields.next()?.parse().ok()?)?;
+                let outcome = parse_outcome_name(fields.next()?)?;
+                let answer = fields.next()?.to_string();
+                Some(Entry {
+                    part,
+                    answer,
+                    outcome,
+                })
+            })
+            .collect();
+        Ok(Ledger { path, entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The answer adventofcode.com accepted for `part`, if any
+    pub fn accepted(&self, part: Part) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.part == part && entry.outcome == SubmitOutcome::Correct)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// Checks `answer` against everything known about `part` before it gets submitted
+    pub fn check(&self, part: Part, answer: &str) -> Result<(), Rejection> {
+        if let Some(accepted) = self.accepted(part) {
+            return Err(Rejection::AlreadySolved {
+                accepted: accepted.to_string(),
+            });
+        }
+
+        let entries = self.entries.iter().filter(|entry| entry.part == part);
+        let mut too_high: Option<(i128, &str)> = None;
+        let mut too_low: Option<(i128, &str)> = None;
+        for entry in entries {
+            if entry.answer == answer {
+                return Err(Rejection::KnownWrong {
+                    outcome: entry.outcome,
+                });
+            }
+            let Ok(value) = entry.answer.parse::<i128>() else {
+                continue;
+            };
+            match entry.outcome {
+                SubmitOutcome::TooHigh if too_high.is_none_or(|(bound, _)| value < bound) => {
+                    too_high = Some((value, &entry.answer));
+                }
+                SubmitOutcome::TooLow if too_low.is_none_or(|(bound, _)| value > bound) => {
+                    too_low = Some((value, &entry.answer));
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some((_, bound)) = too_high.filter(|(bound, _)| value >= *bound) {
+                return Err(Rejection::TooHigh {
+                    bound: bound.to_string(),
+                });
+            }
+            if let Some((_, bound)) = too_low.filter(|(bound, _)| value <= *bound) {
+                return Err(Rejection::TooLow {
+                    bound: bound.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Records how adventofcode.com judged `answer`. Outcomes that say nothing about the
+    /// answer, like being rate limited, are not recorded.
+    pub fn record(
+        &mut self,
+        part: Part,
+        answer: &str,
+        outcome: SubmitOutcome,
+    ) -> Result<(), InputError> {
+        let Some(name) = outcome_name(outcome) else {
+            return Ok(());
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error(dir))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error(&self.path))?;
+        writeln!(file, "{}\t{}\t{}", part.level(), name, answer).map_err(io_error(&self.path))?;
+        self.entries.push(Entry {
+            part,
+            answer: answer.to_string(),
+            outcome,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(entries: &[(Part, &str, SubmitOutcome)]) -> Ledger {
+        Ledger {
+            path: PathBuf::new(),
+            entries: entries
+                .iter()
+                .map(|(part, answer, outcome)| Entry {
+                    part: *part,
+                    answer: answer.to_string(),
+                    outcome: *outcome,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_check_bounds() {
+        let ledger = ledger(&[
+            (Part::One, "500", SubmitOutcome::TooHigh),
+            (Part::One, "800", SubmitOutcome::TooHigh),
+            (Part::One, "100", SubmitOutcome::TooLow),
+            (Part::One, "321", SubmitOutcome::Wrong),
+        ]);
+        assert_eq!(ledger.check(Part::One, "300"), Ok(()));
+        assert_eq!(
+            ledger.check(Part::One, "600"),
+            Err(Rejection::TooHigh {
+                bound: "500".to_string()
+            })
+        );
+        assert_eq!(
+            ledger.check(Part::One, "50"),
+            Err(Rejection::TooLow {
+                bound: "100".to_string()
+            })
+        );
+        assert_eq!(
+            ledger.check(Part::One, "321"),
+            Err(Rejection::KnownWrong {
+                outcome: SubmitOutcome::Wrong
+            })
+        );
+        assert_eq!(ledger.check(Part::Two, "600"), Ok(()));
+    }
+
+    #[test]
+    fn test_check_accepted() {
+        let ledger = ledger(&[
+            (Part::One, "42", SubmitOutcome::TooLow),
+            (Part::One, "43", SubmitOutcome::Correct),
+        ]);
+        assert_eq!(ledger.accepted(Part::One), Some("43"));
+        assert_eq!(ledger.accepted(Part::Two), None);
+        assert_eq!(
+            ledger.check(Part::One, "44"),
+            Err(Rejection::AlreadySolved {
+                accepted: "43".to_string()
+            })
+        );
+    }
+}
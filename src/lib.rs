@@ -1 +1,57 @@
-pub mod input;
\ No newline at end of file
+use std::fmt;
+
+pub mod cache;
+pub mod client;
+pub mod config;
+pub mod embedded;
+pub mod example;
+pub mod html;
+pub mod input;
+pub mod leaderboard;
+pub mod ledger;
+pub mod parse;
+pub mod puzzle;
+pub mod runner;
+pub mod schedule;
+pub mod session;
+pub mod solution;
+pub mod source;
+pub mod stats;
+pub mod submit;
+pub mod testing;
+
+pub use solution::Solution;
+
+/// One of the two parts of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// The `level` adventofcode.com uses for this part
+    pub fn level(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_level(level: u8) -> Option<Part> {
+        match level {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
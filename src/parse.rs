@@ -0,0 +1,299 @@
+//! Parsers for the shapes puzzle inputs keep coming in, built with nom. Each function returns a
+//! nom parser, so they can be combined with each other and with the combinators of nom, and
+//! [`parse_all`] runs the result on a whole input. Inputs that can't be parsed give a
+//! [`ParseError`] pointing at the offending character.
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        char, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+    },
+    combinator::{all_consuming, opt, recognize},
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    Finish, IResult, Offset, Parser,
+};
+use std::{error, fmt, str::FromStr};
+
+/// Where and why an input could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error in characters, starting at 1
+    pub column: usize,
+    /// The whole line containing the error
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at the start of `position`, which has to be a slice of `input`
+    pub fn at(input: &str, position: &str, message: impl Into<String>) -> ParseError {
+        let offset = input.offset(position);
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Renders the error like a compiler diagnostic, with `source` naming the input
+    pub fn render(&self, source: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // Keeps tabs so the marker lines up with the character in the snippet
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        format!(
+            "error: {}\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}^\n",
+            self.message, source, self.line, self.column, self.line, self.snippet, indent
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} in line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Describes the error nom ran into at the start of `rest`
+fn describe(kind: ErrorKind, rest: &str) -> String {
+    let Some(found) = rest.chars().next() else {
+        return "unexpected end of input".to_string();
+    };
+    let found = match found {
+        '\n' | '\r' => "the end of the line".to_string(),
+        found => format!("`{}`", found),
+    };
+    match kind {
+        ErrorKind::Digit => format!("expected a number, found {}", found),
+        ErrorKind::MapRes => "the number is out of range".to_string(),
+        ErrorKind::CrLf => format!("expected the end of the line, found {}", found),
+        ErrorKind::Space => format!("expected a space, found {}", found),
+        _ => format!("unexpected {}", found),
+    }
+}
+
+/// Parses the number recognized by `number`. A number that doesn't fit into `T` is a
+/// failure nom doesn't backtrack from, so the error points at it.
+fn number<'a, T: FromStr>(
+    mut number: impl Parser<&'a str, &'a str, Error<&'a str>>,
+    input: &'a str,
+) -> IResult<&'a str, T> {
+    let (rest, digits) = number.parse(input)?;
+    match digits.parse() {
+        Ok(number) => Ok((rest, number)),
+        Err(_) => Err(nom::Err::Failure(Error::new(input, ErrorKind::MapRes))),
+    }
+}
+
+/// An unsigned integer, e.g. `42`
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    number(digit1, input)
+}
+
+/// An integer with an optional sign, e.g. `-42` or `+42`
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    number(recognize(pair(opt(one_of("+-")), digit1)), input)
+}
+
+/// Values separated by spaces or tabs, e.g. `7 6 4 2 1`
+pub fn spaced<'a, O, F>(value: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(space1, value)
+}
+
+/// Values separated by commas and optional spaces, e.g. `75,47,61` or `1, 2, 3`
+pub fn comma_separated<'a, O, F>(value: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(char(','), space0), value)
+}
+
+/// A key and its values separated by a colon, e.g. `190: 10 19`
+pub fn key_values<'a, K, V, F, G>(
+    key: F,
+    values: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, pair(char(':'), space0), values)
+}
+
+/// Two values separated by a pipe, e.g. the ordering rule `47|53`
+pub fn rule<'a, A, B, F, G>(left: F, right: G) -> impl FnMut(&'a str) -> IResult<&'a str, (A, B)>
+where
+    F: Parser<&'a str, A, Error<&'a str>>,
+    G: Parser<&'a str, B, Error<&'a str>>,
+{
+    separated_pair(left, char('|'), right)
+}
+
+/// One value per line. The lines end at an empty line or the end of the input, and an error
+/// in any line is reported where it occurs instead of ending the lines before it.
+pub fn lines<'a, O, F>(mut line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    move |input| {
+        let (mut rest, first) = line.parse(input)?;
+        let mut values = vec![first];
+        loop {
+            let Ok((next, _)) = line_ending::<_, Error<_>>(rest) else {
+                return Ok((rest, values));
+            };
+            if next.is_empty() || next.starts_with(['\n', '\r']) {
+                return Ok((rest, values));
+            }
+            let (next, value) = line.parse(next).map_err(|err| match err {
+                nom::Err::Error(err) => nom::Err::Failure(err),
+                err => err,
+            })?;
+            values.push(value);
+            rest = next;
+        }
+    }
+}
+
+/// An empty line between two blocks
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    pair(line_ending, line_ending).map(|_| ()).parse(input)
+}
+
+/// Blocks of lines separated by empty lines
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(blank_line, block)
+}
+
+/// A grid of characters, one row per line, with every character turned into a cell by `cell`
+pub fn grid<'a, O, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>>
+where
+    F: Fn(char) -> O,
+{
+    lines(many1(none_of("\r\n").map(cell)))
+}
+
+/// Coordinates following a label, like `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400`
+pub fn labelled_coordinates<'a, T: FromStr>(
+    label: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T)> {
+    let coordinate = |axis| preceded(pair(char(axis), alt((char('+'), char('=')))), signed);
+    preceded(
+        tuple((tag(label), char(':'), space0)),
+        separated_pair(coordinate('X'), pair(char(','), space0), coordinate('Y')),
+    )
+}
+
+/// Runs `parser` on the whole input, apart from trailing whitespace
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    let mut parser = all_consuming(terminated(parser, multispace0));
+    parser(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::at(input, err.input, describe(err.code, err.input)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsers() {
+        assert_eq!(
+            parse_all(lines(spaced(signed::<i32>)), "7 6 -4\n1  +2\n"),
+            Ok(vec![vec![7, 6, -4], vec![1, 2]])
+        );
+        assert_eq!(
+            parse_all(
+                key_values(unsigned::<u64>, spaced(unsigned::<u64>)),
+                "190: 10 19"
+            ),
+            Ok((190, vec![10, 19]))
+        );
+        assert_eq!(
+            parse_all(
+                separated_pair(
+                    lines(rule(unsigned::<u8>, unsigned::<u8>)),
+                    blank_line,
+                    lines(comma_separated(unsigned::<u8>))
+                ),
+                "47|53\n97|13\n\n75,47\n61, 13"
+            ),
+            Ok((vec![(47, 53), (97, 13)], vec![vec![75, 47], vec![61, 13]]))
+        );
+        assert_eq!(
+            parse_all(grid(|c| c == '#'), "#.\n.#"),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(
+            parse_all(
+                blocks(separated_pair(
+                    labelled_coordinates::<i64>("Button A"),
+                    line_ending,
+                    labelled_coordinates::<i64>("Prize")
+                )),
+                "Button A: X+94, Y+34\nPrize: X=8400, Y=5400\n\nButton A: X+1, Y+2\nPrize: X=-3, Y=4"
+            ),
+            Ok(vec![((94, 34), (8400, 5400)), ((1, 2), (-3, 4))])
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_all(lines(spaced(unsigned::<u32>)), "1 2\n1\tx\n").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected `x` in line 2, column 3");
+        assert_eq!(
+            err.render("input"),
+            "error: unexpected `x`
+ --> input:2:3
+  |
+2 | 1\tx
+  |  \t^
+"
+        );
+        let err = parse_all(lines(spaced(unsigned::<u8>)), "1 2 300").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the number is out of range in line 1, column 5"
+        );
+        let err = parse_all(
+            lines(separated_pair(unsigned::<u8>, space1, unsigned::<u8>)),
+            "3 4\n8 x1\n",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected a number, found `x` in line 2, column 3"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use reqwest::StatusCode;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cache, client, config,
+    example::{example_path, ExpectedAnswers, DEFAULT_EXAMPLE},
+    html::{self, Token},
+    input::InputError,
+    schedule,
+};
+
+/// One part of the puzzle description, i.e. one `<article class="day-desc">` of the page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Article {
+    /// The content of every `<pre><code>` block
+    pub code_blocks: Vec<String>,
+    /// Index of the first code block that follows a paragraph mentioning an example
+    pub example_block: Option<usize>,
+    /// The last emphasized code in the article, which is the answer for the example
+    pub example_answer: Option<String>,
+}
+
+impl Article {
+    /// The code block that most likely is the example input. Falls back to the first block.
+    pub fn default_example(&self) -> Option<&str> {
+        self.code_blocks
+            .get(self.example_block.unwrap_or(0))
+            .map(String::as_str)
+    }
+}
+
+/// Extracts the code blocks and example answers of every part shown on a puzzle page
+pub fn parse_articles(page: &str) -> Vec<Article> {
+    let mut articles = Vec::new();
+    let mut article: Option<Article> = None;
+    // Text of the current paragraph, used to find the paragraph introducing the example
+    let mut paragraph = String::new();
+    let mut mentions_example = false;
+    let mut in_pre = false;
+    let mut code: Option<String> = None;
+    let mut em_depth = 0;
+    let mut emphasized = false;
+
+    for token in html::tokenize(page) {
+        match token {
+            Token::Open {
+                name: "article", ..
+            } => article = Some(Article::default()),
+            Token::Close("article") => articles.extend(article.take()),
+            _ if article.is_none() => {}
+            Token::Open { name: "p", .. } => paragraph.clear(),
+            Token::Close("p") => {
+                mentions_example = paragraph.to_lowercase().contains("example");
+            }
+            Token::Open { name: "pre", .. } => in_pre = true,
+            Token::Close("pre") => in_pre = false,
+            Token::Open { name: "code", .. } => {
+                code = Some(String::new());
+                emphasized = em_depth > 0;
+            }
+            Token::Close("code") => {
+                let (Some(article), Some(code)) = (article.as_mut(), code.take()) else {
+                    continue;
+                };
+                let code = html::decode_entities(&code);
+                if in_pre {
+                    if mentions_example && article.example_block.is_none() {
+                        article.example_block = Some(article.code_blocks.len());
+                    }
+                    article.code_blocks.push(code);
+                    mentions_example = false;
+                } else if emphasized {
+                    article.example_answer = Some(code.trim().to_string());
+                }
+            }
+            Token::Open { name: "em", .. } => {
+                em_depth += 1;
+                if code.is_some() {
+                    emphasized = true;
+                }
+            }
+            Token::Close("em") => em_depth -= 1,
+            Token::Text(text) => {
+                paragraph.push_str(text);
+                if let Some(code) = code.as_mut() {
+                    code.push_str(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    articles
+}
+
+/// Downloads the puzzle page of `day` in `year`. The session is optional here, but without it
+/// the page only shows part one.
+pub fn fetch_puzzle_page(year: u16, day: u8) -> Result<String, InputError> {
+    schedule::check_unlocked(year, day)?;
+
+    let session = config::session().ok();
+    let response = client::get(&format!("/{}/day/{}", year, day), session.as_deref())?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(InputError::NotUnlocked {
+            year,
+            day,
+            unlocks_in: schedule::time_until_unlock(year, day),
+        });
+    }
+    Ok(response.error_for_status()?.text()?)
+}
+
+/// Name under which an example that only appears in part two is stored
+pub const PART_TWO_EXAMPLE: &str = "part-two";
+
+/// Stores `example` with its expected answers. An existing, non-empty example is only
+/// replaced if `overwrite` is set.
+pub fn save_example(
+    path: &Path,
+    example: &str,
+    answers: &ExpectedAnswers,
+    overwrite: bool,
+) -> Result<bool, InputError> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    if !existing.is_empty() && !overwrite {
+        return Ok(false);
+    }
+    cache::write_atomic(path, example)?;
+    answers.save(path)?;
+    Ok(true)
+}
+
+/// Picks up part two after part one was solved: stores its expected answer, and its example
+/// if part two comes with a new one. A cached puzzle description is updated as well. Returns
+/// the files that were written.
+pub fn refresh_after_part_one(year: u16, day: u8) -> Result<Vec<PathBuf>, InputError> {
+    let page = fetch_puzzle_page(year, day)?;
+    let mut written = Vec::new();
+
+    let path = puzzle_path(year, day);
+    if path.exists() {
+        write_markdown(&path, &page)?;
+        written.push(path);
+    }
+
+    let articles = parse_articles(&page);
+    let [part_one, part_two] = articles.as_slice() else {
+        return Ok(written);
+    };
+    let Some(answer) = part_two.example_answer.clone() else {
+        return Ok(written);
+    };
+
+    // Part two often reuses the example of part one or just continues with it
+    let new_example = part_two
+        .example_block
+        .map(|index| part_two.code_blocks[index].as_str())
+        .filter(|example| Some(*example) != part_one.default_example());
+    let Some(example) = new_example else {
+        let path = example_path(year, day, DEFAULT_EXAMPLE);
+        let mut answers = ExpectedAnswers::load(&path)?;
+        answers.part_two = Some(answer);
+        answers.save(&path)?;
+        written.push(ExpectedAnswers::path(&path));
+        return Ok(written);
+    };
+
+    let path = example_path(year, day, PART_TWO_EXAMPLE);
+    let answers = ExpectedAnswers {
+        part_one: None,
+        part_two: Some(answer),
+    };
+    save_example(&path, example, &answers, true)?;
+    written.extend([ExpectedAnswers::path(&path), path]);
+    Ok(written)
+}
+
+/// Directory holding the puzzle descriptions of `year`
+pub fn puzzles_dir(year: u16) -> PathBuf {
+    config::year_dir(year).join("puzzles")
+}
+
+/// The file holding the puzzle description of `day` in `year` as Markdown
+pub fn puzzle_path(year: u16, day: u8) -> PathBuf {
+    puzzles_dir(year).join(format!("{}.md", day))
+}
+
+/// Converts the puzzle description on a puzzle page to Markdown. Everything outside of the
+/// `<article>` elements, like navigation and the answer form, is left out.
+pub fn to_markdown(page: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_article = false;
+    let mut in_pre = false;
+    let mut code: Option<String> = None;
+    let mut em_in_code = false;
+    let mut links = Vec::new();
+
+    for token in html::tokenize(page) {
+        match token {
+            Token::Open {
+                name: "article", ..
+            } => in_article = true,
+            Token::Close("article") => in_article = false,
+            _ if !in_article => {}
+            Token::Open { name: "h2", .. } => markdown.push_str("## "),
+            Token::Close("h2") | Token::Close("p") | Token::Close("ul") => {
+                markdown.push_str("\n\n")
+            }
+            Token::Open { name: "li", .. } => markdown.push_str("- "),
+            Token::Close("li") => markdown.push('\n'),
+            Token::Open { name: "pre", .. } => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            Token::Close("pre") => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            Token::Open { name: "code", .. } if !in_pre => {
+                code = Some(String::new());
+                em_in_code = false;
+            }
+            Token::Close("code") if !in_pre => {
+                let content = code.take().unwrap_or_default();
+                let content = html::decode_entities(&content);
+                // Backticks inside the code need a longer fence
+                let fence = if content.contains('`') { "``" } else { "`" };
+                let padding = if content.contains('`') { " " } else { "" };
+                let code = format!("{fence}{padding}{content}{padding}{fence}");
+                match em_in_code {
+                    true => markdown.push_str(&format!("**{}**", code)),
+                    false => markdown.push_str(&code),
+                }
+            }
+            Token::Open { name: "em", .. } | Token::Close("em") if !in_pre => match code {
+                Some(_) => em_in_code = true,
+                None => markdown.push_str("**"),
+            },
+            Token::Open {
+                name: "a",
+                attributes,
+            } => {
+                let href = html::attribute(attributes, "href").unwrap_or("");
+                let href = match href.starts_with('/') {
+                    true => format!("{}{}", config::base_url(), href),
+                    false => href.to_string(),
+                };
+                links.push(href);
+                markdown.push('[');
+            }
+            Token::Close("a") => {
+                markdown.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+            }
+            Token::Text(text) => match code.as_mut() {
+                Some(code) => code.push_str(text),
+                None if in_pre => markdown.push_str(&html::decode_entities(text)),
+                None => {
+                    let text = html::decode_entities(text).replace('\n', " ");
+                    match markdown.is_empty() || markdown.ends_with('\n') {
+                        true => markdown.push_str(text.trim_start()),
+                        false => markdown.push_str(&text),
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    markdown.trim_end().to_string() + "\n"
+}
+
+fn write_markdown(path: &Path, page: &str) -> Result<String, InputError> {
+    let markdown = to_markdown(page);
+    if markdown.trim().is_empty() {
+        return Err(InputError::UnexpectedResponse {
+            reason: "the puzzle page has no puzzle description".to_string(),
+        });
+    }
+    cache::write_atomic(path, &markdown)?;
+    Ok(markdown)
+}
+
+/// Returns the puzzle description of `day` in `year` as Markdown, downloading it if it is not
+/// cached yet. With `refresh`, a cached description that only contains part one is
+/// downloaded again to pick up part two.
+pub fn get_puzzle(year: u16, day: u8, refresh: bool) -> Result<String, InputError> {
+    let path = puzzle_path(year, day);
+    if let Ok(markdown) = fs::read_to_string(&path) {
+        if !refresh || markdown.contains("--- Part Two ---") {
+            return Ok(markdown);
+        }
+    }
+    write_markdown(&path, &fetch_puzzle_page(year, day)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>The lists are not very similar.</p>
+<pre><code>ignored
+</code></pre>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In the example above, this is <code>2</code>, the total is <code><em>11</em></code>!</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The list uses <code>&lt;</code> characters. For these example lists, the score is <em><code>31</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            to_markdown(PAGE),
+            "## --- Day 1: Historian Hysteria ---
+
+The lists are not very similar.
+
+```
+ignored
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+In the example above, this is `2`, the total is **`11`**!
+
+## --- Part Two ---
+
+The list uses `<` characters. For these example lists, the score is **`31`**.
+"
+        );
+    }
+
+    #[test]
+    fn test_parse_articles() {
+        let articles = parse_articles(PAGE);
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[0].code_blocks.len(), 2);
+        assert_eq!(articles[0].default_example(), Some("3   4\n4   3\n"));
+        assert_eq!(articles[0].example_answer.as_deref(), Some("11"));
+        assert_eq!(articles[1].default_example(), None);
+        assert_eq!(articles[1].example_answer.as_deref(), Some("31"));
+    }
+}
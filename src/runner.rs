@@ -0,0 +1,357 @@
+use std::{
+    cell::Cell,
+    env,
+    fmt::Display,
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    time::Instant,
+};
+
+use crate::{
+    config,
+    example::{self, Example},
+    input::{self, io_error, InputError},
+    ledger::Ledger,
+    parse::ParseError,
+    source::{SourceChain, StdinSource, OWN_INPUT_SOURCES},
+    stats,
+    submit::{self, SubmitOutcome},
+    Part, Solution,
+};
+
+const USAGE: &str = "\
+Options:
+    --input <path>      Read the input from a file instead of the configured input sources
+    --stdin             Read the input from stdin, also if it is a pipe
+    --example [name]    Run on an example, by default the first one, and compare the answers
+                        with the expected ones
+    --part <1|2>        Only solve the given part
+    --submit <1|2>      Submit the answer of the given part to adventofcode.com
+    --profile <name>    Use the session, input and answers of another profile
+    --all-profiles      Solve the input of every profile and compare the answers with the
+                        accepted ones
+
+Input is also read from stdin if it is redirected from a file, see AOC_INPUT_SOURCES.";
+
+/// Drives the `main` of a day binary: loads the input, prints the answers and handles the
+/// command line options shared by all days. The runtime of every part solved on the own
+/// puzzle input is recorded, see [`stats::record_runtime`].
+pub struct Runner {
+    day: u8,
+    part: Option<Part>,
+    submit: Option<Part>,
+    sources: SourceChain,
+    /// The example to run on instead of the puzzle input
+    example: Option<Example>,
+    /// The name of the source the input was loaded from, see [`OWN_INPUT_SOURCES`]
+    source: Cell<Option<&'static str>>,
+}
+
+fn parse_part(option: &str, part: Option<String>) -> Option<Part> {
+    part.and_then(|part| Part::from_level(part.parse().ok()?))
+        .or_else(|| exit_with_usage(&format!("{} expects the part, 1 or 2", option)))
+}
+
+impl Runner {
+    /// Parses the command line options. Prints the usage and exits on `--help` and invalid
+    /// options.
+    pub fn new(day: u8) -> Self {
+        let (mut part, mut submit, mut input, mut stdin) = (None, None, None, false);
+        let mut example: Option<Option<String>> = None;
+        let mut all_profiles = false;
+        // The options passed on when running the day for every profile
+        let mut forwarded = Vec::new();
+        let mut args = env::args().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--profile" => {
+                    let profile = args
+                        .next()
+                        .unwrap_or_else(|| exit_with_usage("--profile expects a name"));
+                    env::set_var("AOC_PROFILE", profile);
+                    continue;
+                }
+                "--all-profiles" => {
+                    all_profiles = true;
+                    continue;
+                }
+                "--input" => {
+                    input = args
+                        .next()
+                        .map(PathBuf::from)
+                        .or_else(|| exit_with_usage("--input expects a path"));
+                }
+                "--stdin" => stdin = true,
+                "--example" => {
+                    example = Some(args.next_if(|name| !name.starts_with("--")));
+                }
+                "--part" => {
+                    part = parse_part("--part", args.next());
+                    forwarded.extend(["--part".to_string(), part.unwrap().level().to_string()]);
+                }
+                "--submit" => submit = parse_part("--submit", args.next()),
+                "--help" | "-h" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                _ => exit_with_usage(&format!("Unknown option: {}", arg)),
+            }
+        }
+        if [input.is_some(), stdin, example.is_some()]
+            .iter()
+            .filter(|given| **given)
+            .count()
+            > 1
+        {
+            exit_with_usage("Only one of --input, --stdin and --example can be given");
+        }
+        if submit.is_some() && example.is_some() {
+            exit_with_usage("The answers of examples can't be submitted");
+        }
+        if submit.is_some() && (input.is_some() || stdin) {
+            exit_with_usage("Only answers of your own puzzle input can be submitted");
+        }
+        if part.is_some() && submit.is_some() && part != submit {
+            exit_with_usage("--submit needs the submitted part to be solved");
+        }
+        if all_profiles {
+            if input.is_some() || stdin || example.is_some() || submit.is_some() {
+                exit_with_usage("--all-profiles only works with --part");
+            }
+            run_all_profiles(day, &forwarded);
+        }
+
+        let sources = match stdin {
+            true => SourceChain::new(vec![Box::new(StdinSource { pipes: true })]),
+            false => SourceChain::from_config(input).unwrap_or_else(|err| exit_with_error(err)),
+        };
+        let example = example.map(|name| load_example(day, name));
+        Runner {
+            day,
+            part,
+            submit,
+            sources,
+            example,
+            source: Cell::new(None),
+        }
+    }
+
+    /// Parses the command line options and solves the parts of `S` on its input, see
+    /// [`Runner::new`]
+    pub fn solve<S: Solution>() {
+        let runner = Runner::new(S::DAY);
+        let input = runner.input();
+        let mut parsed = runner.parse(&input, S::parse);
+        runner.part_one(|| S::part_one(&mut parsed));
+        runner.part_two(|| S::part_two(&mut parsed));
+    }
+
+    /// Returns the puzzle input of the day from the first input source that has it, or the
+    /// example given with `--example`. Prints the error and exits if it can't be loaded.
+    pub fn input(&self) -> String {
+        if let Some(example) = &self.example {
+            return example.input.clone();
+        }
+        let year = config::year().unwrap_or_else(|err| exit_with_error(err));
+        let (input, source) = self
+            .sources
+            .load(year, self.day)
+            .unwrap_or_else(|err| exit_with_error(err));
+        self.source.set(Some(source));
+        input
+    }
+
+    /// Parses `input` with `parse`. Prints the error like a compiler diagnostic and exits if
+    /// the input is invalid.
+    pub fn parse<'a, T>(
+        &self,
+        input: &'a str,
+        parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+    ) -> T {
+        parse(input).unwrap_or_else(|err| {
+            let source = match &self.example {
+                Some(example) => format!("example {}", example.name),
+                None => "input".to_string(),
+            };
+            eprint!("{}", err.render(&source));
+            process::exit(1);
+        })
+    }
+
+    /// Solves part one and prints, and if requested submits, the answer
+    pub fn part_one<T: Display>(&self, solve: impl FnOnce() -> T) {
+        self.run(Part::One, solve)
+    }
+
+    /// Solves part two and prints, and if requested submits, the answer
+    pub fn part_two<T: Display>(&self, solve: impl FnOnce() -> T) {
+        self.run(Part::Two, solve)
+    }
+
+    fn run<T: Display>(&self, part: Part, solve: impl FnOnce() -> T) {
+        if self.part.is_some_and(|only| only != part) {
+            return;
+        }
+        let start = Instant::now();
+        let answer = solve().to_string();
+        let runtime = start.elapsed();
+        println!("Part {}: {}", part, answer);
+
+        if let Some(example) = &self.example {
+            match example.answers.get(part) {
+                Some(expected) if expected != answer => eprintln!(
+                    "Warning: the expected answer of part {} for example {} is {}",
+                    part, example.name, expected
+                ),
+                Some(_) => {}
+                None => eprintln!(
+                    "No expected answer of part {} for example {}",
+                    part, example.name
+                ),
+            }
+            return;
+        }
+
+        let year = config::year().unwrap_or_else(|err| exit_with_error(err));
+        // Answers of other inputs say nothing about the accepted ones and must not be submitted,
+        // and their runtimes don't belong next to the own leaderboard times
+        let source = self.source.get().unwrap_or("none");
+        if !OWN_INPUT_SOURCES.contains(&source) {
+            if self.submit == Some(part) {
+                eprintln!(
+                    "Not submitting, the input came from {} instead of adventofcode.com",
+                    source
+                );
+                process::exit(1);
+            }
+            return;
+        }
+        if let Err(err) = stats::record_runtime(year, self.day, part, runtime) {
+            eprintln!("Warning: the runtime could not be recorded: {}", err);
+        }
+        let ledger = Ledger::load(year, self.day).unwrap_or_else(|err| exit_with_error(err));
+        let accepted = ledger.accepted(part);
+        if let Some(accepted) = accepted.filter(|accepted| *accepted != answer) {
+            eprintln!(
+                "Warning: the accepted answer of part {} is {}",
+                part, accepted
+            );
+        }
+
+        if self.submit == Some(part) {
+            if accepted == Some(answer.as_str()) {
+                println!("This answer was already accepted");
+                return;
+            }
+            let submission = submit::submit_answer(year, self.day, part, &answer)
+                .unwrap_or_else(|err| exit_with_error(err));
+            match submission.refreshed {
+                Some(Ok(written)) => {
+                    for path in written {
+                        println!("Updated {}", path.display());
+                    }
+                }
+                Some(Err(err)) => {
+                    eprintln!("Warning: could not fetch the example of part two: {}", err)
+                }
+                None => {}
+            }
+            let outcome = submission.outcome;
+            println!("{}", outcome);
+            if outcome != SubmitOutcome::Correct && outcome != SubmitOutcome::AlreadySolved {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Runs the day binary once for every profile and compares the answers with the ones each
+/// profile got accepted. Exits with an error if any profile fails or disagrees.
+fn run_all_profiles(day: u8, args: &[String]) -> ! {
+    let year = config::year().unwrap_or_else(|err| exit_with_error(err));
+    let profiles = config::profiles(year).unwrap_or_else(|err| exit_with_error(err));
+    let exe = env::current_exe().unwrap_or_else(|err| exit_with_error(io_error("<exe>")(err)));
+    let (mut disagreements, mut failures) = (0, 0);
+    for profile in profiles {
+        let name = profile.as_deref().unwrap_or("default");
+        let cached = input::input_path(year, profile.as_deref(), day).exists();
+        if !cached && config::session_for(profile.as_deref()).is_err() {
+            println!("{}: no input and no session", name);
+            continue;
+        }
+
+        let output = Command::new(&exe)
+            .args(args)
+            .env("AOC_PROFILE", profile.as_deref().unwrap_or(""))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap_or_else(|err| exit_with_error(io_error(&exe)(err)));
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            println!("{}: failed: {}", name, stderr.trim());
+            failures += 1;
+            continue;
+        }
+
+        let ledger = Ledger::load_for(year, day, profile.as_deref())
+            .unwrap_or_else(|err| exit_with_error(err));
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((part, answer)) = line
+                .strip_prefix("Part ")
+                .and_then(|line| line.split_once(": "))
+            else {
+                continue;
+            };
+            let part = if part == "one" { Part::One } else { Part::Two };
+            match ledger.accepted(part) {
+                Some(accepted) if accepted != answer => {
+                    println!(
+                        "{}: part {}: {}, but {} was accepted",
+                        name, part, answer, accepted
+                    );
+                    disagreements += 1;
+                }
+                Some(_) => println!("{}: part {}: {} (accepted)", name, part, answer),
+                None => println!("{}: part {}: {}", name, part, answer),
+            }
+        }
+    }
+
+    if disagreements > 0 || failures > 0 {
+        eprintln!(
+            "{} answers disagree with the accepted ones, {} profiles failed",
+            disagreements, failures
+        );
+        process::exit(1);
+    }
+    process::exit(0);
+}
+
+/// Loads the example `name`, or the first example of `day`. Prints the error and exits if
+/// there is none.
+fn load_example(day: u8, name: Option<String>) -> Example {
+    let year = config::year().unwrap_or_else(|err| exit_with_error(err));
+    let name = match name {
+        Some(name) => name,
+        None => {
+            let names =
+                example::list_examples(year, day).unwrap_or_else(|err| exit_with_error(err));
+            match names.into_iter().next() {
+                Some(name) => name,
+                // Creates the placeholder to paste the example into
+                None => exit_with_error(example::get_example_for(year, day).unwrap_err()),
+            }
+        }
+    };
+    example::load_example(year, day, &name).unwrap_or_else(|err| exit_with_error(err))
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn exit_with_error(err: InputError) -> ! {
+    eprintln!("{}", err);
+    process::exit(1);
+}
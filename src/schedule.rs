@@ -0,0 +1,162 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::input::InputError;
+
+/// Puzzles unlock at midnight US Eastern time, which is always UTC-5 in December
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// The last puzzle day of `year`. Since 2025 the event only has twelve days.
+pub fn last_day(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Checks that `day` is part of the event in `year`
+pub fn check_day(year: u16, day: u8) -> Result<(), InputError> {
+    if year < 2015 || day == 0 || day > last_day(year) {
+        return Err(InputError::InvalidDay { year, day });
+    }
+    Ok(())
+}
+
+/// Number of days between 1970-01-01 and the given date in the proleptic Gregorian calendar
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The moment the puzzle of `day` in `year` unlocks
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as u32) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+/// How long it takes until the puzzle of `day` in `year` unlocks, `None` if it is unlocked
+pub fn time_until_unlock(year: u16, day: u8) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Fails with [`InputError::NotUnlocked`] if the puzzle of `day` in `year` is still locked
+pub fn check_unlocked(year: u16, day: u8) -> Result<(), InputError> {
+    check_day(year, day)?;
+    match time_until_unlock(year, day) {
+        Some(remaining) => Err(InputError::NotUnlocked {
+            year,
+            day,
+            unlocks_in: Some(remaining),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Formats a duration with its two most significant units, e.g. `3h12m` or `2d4h`
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{}d{}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Formats a duration below a day like a clock, e.g. `01:02:03`, or as `>24h` like
+/// adventofcode.com does for anything longer
+pub fn format_clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds < 86400 {
+        true => format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        false => ">24h".to_string(),
+    }
+}
+
+/// Blocks until the puzzle of `day` in `year` unlocks, showing a countdown
+pub fn wait_for_unlock(year: u16, day: u8) -> Result<(), InputError> {
+    check_day(year, day)?;
+    let Some(remaining) = time_until_unlock(year, day) else {
+        return Ok(());
+    };
+
+    let progress = ProgressBar::new(remaining.as_secs());
+    progress.set_style(
+        ProgressStyle::with_template("{prefix} unlocks in {msg} {wide_bar}")
+            .expect("Invalid progress bar template"),
+    );
+    progress.set_prefix(format!("Day {} of {}", day, year));
+
+    while let Some(remaining) = time_until_unlock(year, day) {
+        progress.set_message(format_duration(remaining));
+        progress.set_position(
+            progress
+                .length()
+                .unwrap_or(0)
+                .saturating_sub(remaining.as_secs()),
+        );
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    progress.finish_and_clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2024, 1),
+            UNIX_EPOCH + Duration::from_secs(1733029200)
+        );
+        // 2015-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(2015, 25),
+            UNIX_EPOCH + Duration::from_secs(1451019600)
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 3600 + 12 * 60 + 5)),
+            "3h12m"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs(2 * 86400 + 4 * 3600)),
+            "2d4h"
+        );
+        assert_eq!(format_duration(Duration::from_secs(90)), "1m30s");
+        assert_eq!(format_duration(Duration::from_secs(7)), "7s");
+    }
+}
@@ -0,0 +1,63 @@
+//! Checks of the session cookie, whether it is accepted and how long it stays valid
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    client,
+    config::{self, StoredSession},
+    html::{self, Token},
+    input::InputError,
+};
+
+/// How long adventofcode.com keeps a session valid, roughly a month
+pub const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How long before the session expires `cargo aoc session expiry` starts warning
+pub const EXPIRY_WARNING: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Finds the name of the logged-in user on a page of adventofcode.com. Pages only show it to
+/// users that are logged in.
+pub fn user_name(page: &str) -> Option<String> {
+    let mut tokens = html::tokenize(page).into_iter();
+    tokens.find(|token| {
+        matches!(token, Token::Open { name: "div", attributes }
+            if html::attribute(attributes, "class") == Some("user"))
+    })?;
+    match tokens.next()? {
+        Token::Text(name) => Some(html::decode_entities(name).trim().to_string()),
+        _ => None,
+    }
+    .filter(|name| !name.is_empty())
+}
+
+/// Checks that adventofcode.com accepts `session` and returns the name of the user it
+/// belongs to
+pub fn check_session(session: &str) -> Result<String, InputError> {
+    let page = client::get(&format!("/{}", config::year()?), Some(session))?
+        .error_for_status()?
+        .text()?;
+    user_name(&page).ok_or(InputError::Unauthorized)
+}
+
+/// How long ago `session` was stored, if it was stored with a timestamp
+pub fn session_age(session: &StoredSession) -> Option<Duration> {
+    let set_at = Duration::from_secs(session.set_at?);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Some(now.saturating_sub(set_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_name() {
+        let page = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<div class="user">Mock &amp; User <span class="star-count">4*</span></div></div></header>"#;
+        assert_eq!(user_name(page).as_deref(), Some("Mock & User"));
+        let page = r#"<div><a href="/2024/auth/login">[Log In]</a></div>"#;
+        assert_eq!(user_name(page), None);
+    }
+}
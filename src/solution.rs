@@ -0,0 +1,23 @@
+//! The interface every day implements, so the [`Runner`](crate::runner::Runner) and
+//! [`check_solution`](crate::testing::check_solution) can drive any of them.
+
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+
+/// The solution of one day's puzzle
+pub trait Solution {
+    /// The day of the puzzle
+    const DAY: u8;
+
+    /// The parsed puzzle input. Both parts get it mutably, so part one can keep what part two
+    /// needs as well, like a path both parts walk. Part two is also solved on its own with
+    /// `--part 2`, so it can't rely on part one having run.
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    fn part_one(parsed: &mut Self::Parsed) -> impl Display;
+
+    fn part_two(parsed: &mut Self::Parsed) -> impl Display;
+}
@@ -0,0 +1,221 @@
+//! Where puzzle inputs come from. Every source either has the input of a day or passes, and
+//! a [`SourceChain`] asks its sources in order until one has it.
+
+use std::{
+    fs::{self, File},
+    io::{self, IsTerminal, Read},
+    path::PathBuf,
+};
+
+use crate::{
+    config, embedded,
+    input::{self, io_error, InputError},
+};
+
+/// The sources used when `AOC_INPUT_SOURCES` is not set
+pub const DEFAULT_SOURCES: &str = "stdin,embedded,cache,http";
+
+/// The sources that load the puzzle input of the selected profile, as opposed to a file or
+/// stdin. Only answers of these inputs are compared with or submitted to
+/// adventofcode.com.
+pub const OWN_INPUT_SOURCES: &[&str] = &["embedded", "cache", "http"];
+
+/// A place puzzle inputs can be loaded from
+pub trait InputSource {
+    /// Name of the source, as used in `AOC_INPUT_SOURCES`
+    fn name(&self) -> &'static str;
+
+    /// Returns the input of `day` in `year`, or `None` if this source doesn't have it
+    fn load(&self, year: u16, day: u8) -> Result<Option<String>, InputError>;
+}
+
+/// The inputs cached in the data directory
+pub struct CacheSource;
+
+impl InputSource for CacheSource {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    fn load(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        input::read_cached_input(year, day)
+    }
+}
+
+/// Downloads the input from adventofcode.com and caches it
+pub struct HttpSource;
+
+impl InputSource for HttpSource {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn load(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        input::download_input(year, day).map(Some)
+    }
+}
+
+/// Whether stdin is redirected from a file, as opposed to a terminal or a pipe
+fn stdin_is_file() -> bool {
+    #[cfg(unix)]
+    let handle = std::os::fd::AsFd::as_fd(&io::stdin()).try_clone_to_owned();
+    #[cfg(windows)]
+    let handle = std::os::windows::io::AsHandle::as_handle(&io::stdin()).try_clone_to_owned();
+    handle
+        .map(File::from)
+        .and_then(|file| file.metadata())
+        .is_ok_and(|metadata| metadata.is_file())
+}
+
+/// Whatever is redirected into stdin. Passes if stdin is a terminal or nothing was piped.
+pub struct StdinSource {
+    /// Whether to read pipes as well as files. A pipe that stays open without anything being
+    /// written to it, like in CI runners or editors, blocks until it is closed, so only an
+    /// explicit `--stdin` reads them.
+    pub pipes: bool,
+}
+
+impl InputSource for StdinSource {
+    fn name(&self) -> &'static str {
+        "stdin"
+    }
+
+    fn load(&self, _year: u16, _day: u8) -> Result<Option<String>, InputError> {
+        let mut stdin = io::stdin();
+        if stdin.is_terminal() || !(self.pipes || stdin_is_file()) {
+            return Ok(None);
+        }
+        let mut input = String::new();
+        stdin
+            .read_to_string(&mut input)
+            .map_err(io_error("<stdin>"))?;
+        Ok(Some(input).filter(|input| !input.is_empty()))
+    }
+}
+
+/// A file given explicitly, used for every day
+pub struct FileSource {
+    pub path: PathBuf,
+}
+
+impl InputSource for FileSource {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn load(&self, _year: u16, _day: u8) -> Result<Option<String>, InputError> {
+        fs::read_to_string(&self.path)
+            .map(Some)
+            .map_err(io_error(&self.path))
+    }
+}
+
+/// Inputs compiled into the binary, see [`embedded`]. They belong to the default profile, so
+/// this source passes if another profile is selected.
+pub struct EmbeddedSource {
+    pub inputs: &'static [(u16, u8, &'static str)],
+}
+
+impl InputSource for EmbeddedSource {
+    fn name(&self) -> &'static str {
+        "embedded"
+    }
+
+    fn load(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        if config::profile()?.is_some() {
+            return Ok(None);
+        }
+        Ok(self
+            .inputs
+            .iter()
+            .find(|input| input.0 == year && input.1 == day)
+            .map(|input| input.2.to_string()))
+    }
+}
+
+/// Input sources that are asked in order
+pub struct SourceChain {
+    sources: Vec<Box<dyn InputSource>>,
+}
+
+impl SourceChain {
+    pub fn new(sources: Vec<Box<dyn InputSource>>) -> Self {
+        SourceChain { sources }
+    }
+
+    /// Builds the chain from the comma separated source names in `AOC_INPUT_SOURCES`, by
+    /// default [`DEFAULT_SOURCES`]. An explicit `path` is always asked first.
+    pub fn from_config(path: Option<PathBuf>) -> Result<Self, InputError> {
+        let names = config::var("AOC_INPUT_SOURCES");
+        let names = names.as_deref().unwrap_or(DEFAULT_SOURCES);
+        let mut sources: Vec<Box<dyn InputSource>> = Vec::new();
+        if let Some(path) = path {
+            sources.push(Box::new(FileSource { path }));
+        }
+        for name in names.split(',').map(str::trim) {
+            sources.push(match name {
+                "stdin" => Box::new(StdinSource { pipes: false }),
+                "embedded" => Box::new(EmbeddedSource {
+                    inputs: embedded::INPUTS,
+                }),
+                "cache" => Box::new(CacheSource),
+                "http" => Box::new(HttpSource),
+                _ => {
+                    return Err(InputError::InvalidConfig {
+                        key: "AOC_INPUT_SOURCES",
+                        value: names.to_string(),
+                    })
+                }
+            });
+        }
+        Ok(SourceChain::new(sources))
+    }
+
+    /// Like [`SourceChain::from_config`], but only with the [`OWN_INPUT_SOURCES`], to load the
+    /// puzzle input of the selected profile
+    pub fn own_from_config() -> Result<Self, InputError> {
+        let mut chain = SourceChain::from_config(None)?;
+        chain
+            .sources
+            .retain(|source| OWN_INPUT_SOURCES.contains(&source.name()));
+        Ok(chain)
+    }
+
+    /// Returns the input of `day` in `year` from the first source that has it, see
+    /// [`input::normalize`], together with the name of that source
+    pub fn load(&self, year: u16, day: u8) -> Result<(String, &'static str), InputError> {
+        for source in &self.sources {
+            if let Some(input) = source.load(year, day)? {
+                return Ok((input::normalize(&input), source.name()));
+            }
+        }
+        Err(InputError::NoSource {
+            year,
+            day,
+            sources: self.sources.iter().map(|source| source.name()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: &[(u16, u8, &str)] = &[(2024, 1, "1 2\n")];
+
+    #[test]
+    fn test_chain_order() {
+        let chain = SourceChain::new(vec![
+            Box::new(EmbeddedSource { inputs: &[] }),
+            Box::new(EmbeddedSource { inputs: INPUTS }),
+        ]);
+        assert_eq!(
+            chain.load(2024, 1).unwrap(),
+            ("1 2".to_string(), "embedded")
+        );
+        assert_eq!(
+            chain.load(2024, 2).unwrap_err().to_string(),
+            "None of the input sources (embedded, embedded) has the input of day 2 of 2024"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Personal statistics: how fast each day was solved according to the personal leaderboard of
+//! adventofcode.com, and how fast the solutions run according to the runner
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path, path::PathBuf, time::Duration};
+
+use crate::{
+    cache, client, config,
+    html::{self, Token},
+    input::{io_error, InputError},
+    schedule, session, Part,
+};
+
+/// Time, rank and score of one part on the personal leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartStats {
+    /// Seconds between the unlock and solving the part, `None` if it took more than a day
+    pub time: Option<u64>,
+    pub rank: u32,
+    pub score: u32,
+}
+
+/// One line of the personal leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayStats {
+    pub day: u8,
+    pub part_one: PartStats,
+    /// `None` if only part one was solved
+    pub part_two: Option<PartStats>,
+}
+
+/// Parses the three columns of one part, or `None` if the part was not solved
+fn parse_part(fields: &[&str]) -> Option<PartStats> {
+    let [time, rank, score] = fields else {
+        return None;
+    };
+    let time = match *time {
+        ">24h" => None,
+        time => {
+            let clock: Vec<u64> = time
+                .split(':')
+                .map(|field| field.parse().ok())
+                .collect::<Option<_>>()?;
+            let [hours, minutes, seconds] = clock.as_slice() else {
+                return None;
+            };
+            Some(hours * 3600 + minutes * 60 + seconds)
+        }
+    };
+    Some(PartStats {
+        time,
+        rank: rank.parse().ok()?,
+        score: score.parse().ok()?,
+    })
+}
+
+/// Extracts the table of the personal leaderboard page, sorted by day
+pub fn parse_personal_stats(page: &str) -> Vec<DayStats> {
+    let mut table = String::new();
+    let mut in_pre = false;
+    for token in html::tokenize(page) {
+        match token {
+            Token::Open { name: "pre", .. } => in_pre = true,
+            Token::Close("pre") => in_pre = false,
+            Token::Text(text) if in_pre => table.push_str(&html::decode_entities(text)),
+            _ => {}
+        }
+    }
+
+    let mut days: Vec<_> = table
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let day = fields.first()?.parse().ok()?;
+            Some(DayStats {
+                day,
+                part_one: parse_part(fields.get(1..4)?)?,
+                part_two: fields.get(4..7).and_then(parse_part),
+            })
+        })
+        .collect();
+    days.sort_by_key(|day| day.day);
+    days
+}
+
+/// Downloads the personal leaderboard of `year` for the configured profile
+pub fn fetch_personal_stats(year: u16) -> Result<Vec<DayStats>, InputError> {
+    let session = config::session()?;
+    let response = client::get(&format!("/{}/leaderboard/self", year), Some(&session))?;
+    let page = response.error_for_status()?.text()?;
+    // Without a valid session the page asks to log in instead of showing the user
+    if session::user_name(&page).is_none() {
+        return Err(InputError::Unauthorized);
+    }
+    Ok(parse_personal_stats(&page))
+}
+
+/// The file holding the personal leaderboard of `profile` in `year`
+pub fn personal_stats_path(year: u16, profile: Option<&str>) -> PathBuf {
+    config::profile_dir(config::year_dir(year).join("stats"), profile).join("personal.json")
+}
+
+/// How long the runner took to solve each part of a day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayRuntimes {
+    /// In microseconds
+    pub part_one: Option<u64>,
+    /// In microseconds
+    pub part_two: Option<u64>,
+}
+
+impl DayRuntimes {
+    pub fn get(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::One => self.part_one,
+            Part::Two => self.part_two,
+        }
+        .map(Duration::from_micros)
+    }
+}
+
+/// The file holding the last runtimes of `year` measured on the input of `profile`
+pub fn runtimes_path(year: u16, profile: Option<&str>) -> PathBuf {
+    config::profile_dir(config::year_dir(year).join("stats"), profile).join("runtimes.json")
+}
+
+/// Reads a JSON file, `None` if it doesn't exist
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, InputError> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error(path)(err)),
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|err| InputError::InvalidFile {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        })
+}
+
+/// Writes `value` as pretty printed JSON
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), InputError> {
+    let json = serde_json::to_string_pretty(value).expect("Stats are always serializable");
+    cache::write_atomic(path, &(json + "\n"))
+}
+
+/// Records how long solving `part` of `day` on the input of the configured profile took,
+/// replacing the previous measurement
+pub fn record_runtime(year: u16, day: u8, part: Part, runtime: Duration) -> Result<(), InputError> {
+    let path = runtimes_path(year, config::profile()?.as_deref());
+    let mut runtimes: BTreeMap<u8, DayRuntimes> = load_json(&path)?.unwrap_or_default();
+    let day = runtimes.entry(day).or_default();
+    let micros = Some(runtime.as_micros() as u64);
+    match part {
+        Part::One => day.part_one = micros,
+        Part::Two => day.part_two = micros,
+    }
+    save_json(&path, &runtimes)
+}
+
+/// Renders the personal leaderboard next to the runtimes, one line per day that has either
+pub fn render(personal: &[DayStats], runtimes: &BTreeMap<u8, DayRuntimes>) -> String {
+    let mut days: Vec<u8> = personal.iter().map(|stats| stats.day).collect();
+    days.extend(runtimes.keys());
+    days.sort();
+    days.dedup();
+
+    let part = |stats: Option<&PartStats>| match stats {
+        Some(stats) => format!(
+            "{:>8}  {:>6}  {:>5}",
+            stats
+                .time
+                .map_or(">24h".to_string(), |time| schedule::format_clock(
+                    Duration::from_secs(time)
+                )),
+            stats.rank,
+            stats.score
+        ),
+        None => format!("{:>8}  {:>6}  {:>5}", "-", "-", "-"),
+    };
+    let runtime = |runtime: Option<Duration>| match runtime {
+        Some(runtime) => format!("{:>9}", format!("{:.2?}", runtime)),
+        None => format!("{:>9}", "-"),
+    };
+
+    let mut text = format!(
+        "{:3}   {:<23}   {:<23}   Runtime\n{:3}   {}   {}   {:>9}  {:>9}\n",
+        "",
+        "Part one",
+        "Part two",
+        "Day",
+        part_header(),
+        part_header(),
+        "Part one",
+        "Part two"
+    );
+    for day in days {
+        let stats = personal.iter().find(|stats| stats.day == day);
+        let runtimes = runtimes.get(&day).cloned().unwrap_or_default();
+        text.push_str(&format!(
+            "{:>3}   {}   {}   {}  {}\n",
+            day,
+            part(stats.map(|stats| &stats.part_one)),
+            part(stats.and_then(|stats| stats.part_two.as_ref())),
+            runtime(runtimes.get(Part::One)),
+            runtime(runtimes.get(Part::Two))
+        ));
+    }
+    text
+}
+
+fn part_header() -> String {
+    format!("{:>8}  {:>6}  {:>5}", "Time", "Rank", "Score")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_personal_stats() {
+        let page = r#"<article><p>These are your personal leaderboard statistics.</p>
+<pre><span class="leaderboard-daydesc-first">      --------Part 1--------   </span><span class="leaderboard-daydesc-both">--------Part 2--------</span>
+Day   <span class="leaderboard-daydesc-first">    Time   Rank  Score</span>   <span class="leaderboard-daydesc-both">    Time   Rank  Score</span>
+  3       &gt;24h  45123      0          -      -      -
+  1   00:13:20   2345      0   01:00:00   3456      0
+</pre>
+</article>"#;
+        assert_eq!(
+            parse_personal_stats(page),
+            vec![
+                DayStats {
+                    day: 1,
+                    part_one: PartStats {
+                        time: Some(800),
+                        rank: 2345,
+                        score: 0
+                    },
+                    part_two: Some(PartStats {
+                        time: Some(3600),
+                        rank: 3456,
+                        score: 0
+                    }),
+                },
+                DayStats {
+                    day: 3,
+                    part_one: PartStats {
+                        time: None,
+                        rank: 45123,
+                        score: 0
+                    },
+                    part_two: None,
+                },
+            ]
+        );
+    }
+}
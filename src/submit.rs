@@ -0,0 +1,154 @@
+use regex::Regex;
+use reqwest::StatusCode;
+use std::{fmt, path::PathBuf, time::Duration};
+
+use crate::{client, config, input::InputError, ledger::Ledger, puzzle, schedule, Part};
+
+/// How adventofcode.com judged a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently, the next one is accepted after the given time
+    RateLimited(Duration),
+    /// The part was already solved, so the answer was not checked
+    AlreadySolved,
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer, it is too high"),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer, it is too low"),
+            SubmitOutcome::Wrong => write!(f, "That's not the right answer"),
+            SubmitOutcome::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {} before trying again",
+                schedule::format_duration(*wait)
+            ),
+            SubmitOutcome::AlreadySolved => write!(f, "This part is already solved"),
+        }
+    }
+}
+
+/// What [`submit_answer`] got back for an answer
+#[derive(Debug)]
+pub struct Submission {
+    pub outcome: SubmitOutcome,
+    /// Once part one is solved, the files [`puzzle::refresh_after_part_one`] wrote for part
+    /// two, or why it failed. Failing doesn't make the submission any less successful.
+    pub refreshed: Option<Result<Vec<PathBuf>, InputError>>,
+}
+
+/// Interprets the page adventofcode.com shows after submitting an answer
+pub fn parse_outcome(page: &str) -> Result<SubmitOutcome, InputError> {
+    if page.contains("That's the right answer") {
+        return Ok(SubmitOutcome::Correct);
+    }
+    if page.contains("You don't seem to be solving the right level") {
+        return Ok(SubmitOutcome::AlreadySolved);
+    }
+    if page.contains("You gave an answer too recently") {
+        let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = re.captures(page).map_or(0, |cap| {
+            let minutes: u64 = cap.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = cap[2].parse().unwrap();
+            minutes * 60 + seconds
+        });
+        return Ok(SubmitOutcome::RateLimited(Duration::from_secs(wait)));
+    }
+    if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            return Ok(SubmitOutcome::TooHigh);
+        }
+        if page.contains("your answer is too low") {
+            return Ok(SubmitOutcome::TooLow);
+        }
+        return Ok(SubmitOutcome::Wrong);
+    }
+    if page.contains("please identify yourself") {
+        return Err(InputError::Unauthorized);
+    }
+    Err(InputError::UnexpectedResponse {
+        reason: "could not find the verdict in the answer page".to_string(),
+    })
+}
+
+/// Submits `answer` for `part` of `day` in `year` and returns how adventofcode.com judged it.
+/// Answers the [`Ledger`] of the day already knows to be wrong are not sent, and the outcome
+/// is recorded in it. Solving part one also picks up part two, see [`Submission::refreshed`].
+pub fn submit_answer(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Submission, InputError> {
+    schedule::check_unlocked(year, day)?;
+    let mut ledger = Ledger::load(year, day)?;
+    ledger
+        .check(part, answer)
+        .map_err(|reason| InputError::AnswerRejected {
+            answer: answer.to_string(),
+            reason,
+        })?;
+    let session = config::session()?;
+
+    let level = part.level().to_string();
+    let response = client::post_form(
+        &format!("/{}/day/{}/answer", year, day),
+        Some(&session),
+        &[("level", &level), ("answer", answer)],
+    )?;
+
+    match response.status() {
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            return Err(InputError::Unauthorized)
+        }
+        _ => {}
+    }
+
+    let outcome = parse_outcome(&response.error_for_status()?.text()?)?;
+    ledger.record(part, answer, outcome)?;
+
+    // Part two is visible now, so its example and expected answer can be fetched
+    let refreshed = (outcome == SubmitOutcome::Correct && part == Part::One)
+        .then(|| puzzle::refresh_after_part_one(year, day));
+    Ok(Submission { outcome, refreshed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_outcome() {
+        let correct = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian.</p></article>";
+        assert_eq!(parse_outcome(correct).unwrap(), SubmitOutcome::Correct);
+
+        let too_high = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again.</p></article>";
+        assert_eq!(parse_outcome(too_high).unwrap(), SubmitOutcome::TooHigh);
+
+        let too_low = "<article><p>That's not the right answer; your answer is too low.  please wait one minute before trying again.</p></article>";
+        assert_eq!(parse_outcome(too_low).unwrap(), SubmitOutcome::TooLow);
+
+        let wrong = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>";
+        assert_eq!(parse_outcome(wrong).unwrap(), SubmitOutcome::Wrong);
+
+        let rate_limited = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+        assert_eq!(
+            parse_outcome(rate_limited).unwrap(),
+            SubmitOutcome::RateLimited(Duration::from_secs(83))
+        );
+
+        let already_solved = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+        assert_eq!(
+            parse_outcome(already_solved).unwrap(),
+            SubmitOutcome::AlreadySolved
+        );
+
+        assert!(parse_outcome("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Helpers for the tests of the day binaries. The expected answers live next to the examples,
+//! see [`ExpectedAnswers`], so adding an example or fixing an answer needs no code changes.
+
+use std::fmt::Display;
+
+use crate::{
+    config,
+    example::{self, Example, ExpectedAnswers},
+    Part, Solution,
+};
+
+/// Solves `part` of every example of `day` that has an expected answer for it and panics with
+/// the examples whose answer differs. Also panics if no example has an expected answer for
+/// the part, so a part can't go untested unnoticed.
+pub fn check_examples<T: Display>(day: u8, part: Part, solve: impl Fn(&str) -> T) {
+    check(day, part, |example| solve(&example.input))
+}
+
+fn check<T: Display>(day: u8, part: Part, solve: impl Fn(&Example) -> T) {
+    let year = config::year().unwrap_or_else(|err| panic!("{}", err));
+    let names = example::list_examples(year, day).unwrap_or_else(|err| panic!("{}", err));
+    if names.is_empty() {
+        // Creates the placeholder to paste the example into
+        panic!("{}", example::get_example_for(year, day).unwrap_err());
+    }
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for name in names {
+        let example =
+            example::load_example(year, day, &name).unwrap_or_else(|err| panic!("{}", err));
+        let Some(expected) = example.answers.get(part) else {
+            continue;
+        };
+        let answer = solve(&example).to_string();
+        if answer != expected {
+            failures.push(format!(
+                "example {}: expected {}, got {}",
+                name, expected, answer
+            ));
+        }
+        checked += 1;
+    }
+
+    if checked == 0 {
+        panic!(
+            "no expected answer of part {} for any example of day {}, add it to {}",
+            part,
+            day,
+            ExpectedAnswers::path(&example::example_path(year, day, example::DEFAULT_EXAMPLE))
+                .display()
+        );
+    }
+    if !failures.is_empty() {
+        panic!(
+            "part {} of day {} is wrong for {} of {} examples:\n{}",
+            part,
+            day,
+            failures.len(),
+            checked,
+            failures.join("\n")
+        );
+    }
+}
+
+/// Checks `part` of `S` on the examples like [`check_examples`]. Part two is also solved after
+/// part one on the same parsed input, as the runner does, and panics if the answers differ.
+/// Examples without an expected answer for part one skip that, since they may be made for
+/// part two only.
+pub fn check_solution<S: Solution>(part: Part) {
+    check(S::DAY, part, |example| {
+        let parse = || {
+            S::parse(&example.input).unwrap_or_else(|err| {
+                panic!("{}", err.render(&format!("example {}", example.name)))
+            })
+        };
+        if part == Part::One {
+            return S::part_one(&mut parse()).to_string();
+        }
+        let answer = S::part_two(&mut parse()).to_string();
+        if example.answers.get(Part::One).is_some() {
+            let mut parsed = parse();
+            S::part_one(&mut parsed);
+            let after_part_one = S::part_two(&mut parsed).to_string();
+            if after_part_one != answer {
+                panic!(
+                    "part two of day {} is {} on its own, but {} after part one",
+                    S::DAY,
+                    answer,
+                    after_part_one
+                );
+            }
+        }
+        answer
+    });
+}
@@ -0,0 +1,517 @@
+//! Runs the `aoc` tool against `aoc-mock`, which serves the fixtures in `tests/fixtures`
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{Child, Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+const SESSION: &str = "mock-session";
+
+struct Mock {
+    server: Child,
+    url: String,
+    data_dir: PathBuf,
+}
+
+impl Mock {
+    fn start(name: &str, args: &[&str]) -> Mock {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_aoc-mock"))
+            .args(["--port", "0", "--fixtures"])
+            .arg(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(server.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let url = line
+            .trim()
+            .strip_prefix("Listening on ")
+            .unwrap()
+            .to_string();
+
+        let data_dir = env::temp_dir().join(format!("aoc24-mock-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&data_dir).ok();
+        Mock {
+            server,
+            url,
+            data_dir,
+        }
+    }
+
+    /// Runs `cargo aoc` with `args` and `session` as the session cookie
+    fn aoc(&self, session: Option<&str>, args: &[&str]) -> Output {
+        self.run(env!("CARGO_BIN_EXE_aoc"), session, args)
+    }
+
+    /// Runs the binary `exe` with `args` and `session` as the session cookie
+    fn run(&self, exe: &str, session: Option<&str>, args: &[&str]) -> Output {
+        self.command(exe, session, args).output().unwrap()
+    }
+
+    /// Like [`Mock::run`], but redirects the file at `path` in the data directory into stdin
+    fn run_redirected(
+        &self,
+        exe: &str,
+        session: Option<&str>,
+        args: &[&str],
+        path: &str,
+    ) -> Output {
+        let stdin = fs::File::open(self.data_dir.join(path)).unwrap();
+        self.command(exe, session, args)
+            .stdin(stdin)
+            .output()
+            .unwrap()
+    }
+
+    fn command(&self, exe: &str, session: Option<&str>, args: &[&str]) -> Command {
+        let mut command = Command::new(exe);
+        command
+            .args(args)
+            // Keeps the .env file of the repository from being picked up
+            .current_dir(env::temp_dir())
+            .env("AOC_BASE_URL", &self.url)
+            .env("AOC_DATA_DIR", &self.data_dir)
+            .env("AOC_CONFIG", self.data_dir.join("config.toml"))
+            .env("AOC_YEAR", "2024")
+            .env("AOC_MIN_INTERVAL", "0")
+            .env_remove("AOC_SESSION")
+            .env_remove("AOC_PROFILE")
+            .stdin(Stdio::null());
+        if let Some(session) = session {
+            command.env("AOC_SESSION", session);
+        }
+        command
+    }
+
+    fn read(&self, path: &str) -> Option<String> {
+        fs::read_to_string(self.data_dir.join(path)).ok()
+    }
+
+    fn write(&self, path: &str, content: &str) {
+        let path = self.data_dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        self.server.kill().ok();
+        self.server.wait().ok();
+        fs::remove_dir_all(&self.data_dir).ok();
+    }
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_fetch_input() {
+    let mock = Mock::start("fetch", &[]);
+    let output = mock.aoc(Some(SESSION), &["fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Fetched day 1 of 2024 (3 lines)\n");
+    assert_eq!(
+        mock.read("2024/inputs/1.txt").as_deref(),
+        Some("3 4\n8 1\n5 9\n")
+    );
+}
+
+#[test]
+fn test_fetch_checks_session() {
+    let mock = Mock::start("session", &[]);
+    let output = mock.aoc(None, &["fetch", "1"]);
+    assert!(stderr(&output).contains("Could not find AOC_SESSION"));
+
+    let output = mock.aoc(Some("expired"), &["fetch", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("The AoC session cookie was rejected"));
+    assert_eq!(mock.read("2024/inputs/1.txt"), None);
+}
+
+#[test]
+fn test_fetch_locked_day() {
+    let mock = Mock::start("locked", &[]);
+    let output = mock.aoc(Some(SESSION), &["fetch", "2"]);
+    assert!(!output.status.success());
+    assert_eq!(stderr(&output), "Day 2 of 2024 has not been unlocked yet\n");
+    assert_eq!(mock.read("2024/inputs/2.txt"), None);
+}
+
+#[test]
+fn test_example_and_puzzle() {
+    let mock = Mock::start("example", &[]);
+    let output = mock.aoc(None, &["example", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        mock.read("2024/examples/1/default.txt").as_deref(),
+        Some("1 2\n3 4\n")
+    );
+    assert_eq!(
+        mock.read("2024/examples/1/default.answers.toml").as_deref(),
+        Some("part_one = \"10\"\n")
+    );
+
+    let output = mock.aoc(None, &["puzzle", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let markdown = mock.read("2024/puzzles/1.md").unwrap();
+    assert!(markdown.starts_with("## --- Day 1: Mock Puzzle ---\n"));
+    assert!(!markdown.contains("Part Two"));
+}
+
+#[test]
+fn test_submit() {
+    let mock = Mock::start("submit", &[]);
+    let submit = |part, answer| stdout(&mock.aoc(Some(SESSION), &["submit", "1", part, answer]));
+    assert_eq!(
+        submit("1", "100"),
+        "That's not the right answer, it is too high\n"
+    );
+    assert_eq!(
+        submit("1", "7"),
+        "That's not the right answer, it is too low\n"
+    );
+    assert_eq!(submit("2", "65"), "This part is already solved\n");
+    assert!(submit("1", "30").ends_with("That's the right answer!\n"));
+
+    // Solving part one picks up the expected answer of part two's example
+    assert_eq!(
+        mock.read("2024/examples/1/default.answers.toml").as_deref(),
+        Some("part_two = \"14\"\n")
+    );
+    assert_eq!(
+        mock.read("2024/answers/1.txt").as_deref(),
+        Some("1\ttoo-high\t100\n1\ttoo-low\t7\n1\tcorrect\t30\n")
+    );
+
+    let output = mock.aoc(Some(SESSION), &["submit", "1", "1", "31"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("Not submitting 31"));
+    assert_eq!(submit("2", "65"), "That's the right answer!\n");
+}
+
+#[test]
+fn test_submit_rate_limited() {
+    let mock = Mock::start("rate-limit", &["--rate-limit", "90"]);
+    let submit = |answer| stdout(&mock.aoc(Some(SESSION), &["submit", "1", "1", answer]));
+    assert_eq!(
+        submit("100"),
+        "That's not the right answer, it is too high\n"
+    );
+    assert_eq!(
+        submit("30"),
+        "You gave an answer too recently, wait 1m30s before trying again\n"
+    );
+    // Rate limited answers are not recorded, so they can be submitted again
+    assert_eq!(
+        mock.read("2024/answers/1.txt").as_deref(),
+        Some("1\ttoo-high\t100\n")
+    );
+}
+
+#[test]
+fn test_session() {
+    let mock = Mock::start("user-config", &[]);
+    let output = mock.aoc(None, &["session", "set", "session=expired"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = mock.aoc(None, &["session", "check"]);
+    assert_eq!(
+        stderr(&output),
+        "The AoC session cookie was rejected. It has probably expired, log in again and run `cargo aoc session set`\n"
+    );
+
+    mock.aoc(None, &["session", "set", SESSION]);
+    let config = mock.read("config.toml").unwrap();
+    assert!(config.starts_with("session = \"mock-session\"\nsession_set_at = "));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = fs::metadata(mock.data_dir.join("config.toml")).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    let output = mock.aoc(None, &["session", "check"]);
+    assert_eq!(stdout(&output), "Logged in as Mock User\n");
+    let output = mock.aoc(None, &["session", "expiry"]);
+    assert!(stdout(&output).ends_with("expires in about 29d23h\n"));
+    // The stored session is used for downloads as well
+    assert!(mock.aoc(None, &["fetch", "1"]).status.success());
+}
+
+#[test]
+fn test_profiles() {
+    let mock = Mock::start("profiles", &[]);
+    mock.aoc(None, &["--profile", "alice", "session", "set", SESSION]);
+    assert!(mock
+        .read("config.toml")
+        .unwrap()
+        .contains("[profiles.alice]"));
+    let output = mock.aoc(None, &["--profile", "alice", "fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(mock.read("2024/inputs/alice/1.txt").is_some());
+    assert_eq!(mock.read("2024/inputs/1.txt"), None);
+
+    // bob has no session, but a cached input and an accepted answer the solution disagrees with
+    mock.write("2024/inputs/bob/1.txt", "1 1\n2 2\n");
+    mock.write("2024/answers/bob/1.txt", "1\tcorrect\t1\n");
+    let output = mock.run(env!("CARGO_BIN_EXE_1"), None, &["--all-profiles"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "default: no input and no session
+alice: part one: 4
+alice: part two: 0
+bob: part one: 0, but 1 was accepted
+bob: part two: 3
+"
+    );
+    // Runtimes are recorded per profile
+    assert!(mock.read("2024/stats/bob/runtimes.json").is_some());
+    assert_eq!(mock.read("2024/stats/runtimes.json"), None);
+}
+
+#[test]
+fn test_verify_cache() {
+    let mock = Mock::start("verify", &[]);
+    mock.aoc(Some(SESSION), &["fetch", "1"]);
+    assert_eq!(
+        mock.read("2024/inputs/SHA256SUMS").as_deref(),
+        Some("ad3f90a92ce3c26d6f48154e06055947243f429badb5cb8770dc051d643991b9  1.txt\n")
+    );
+    let output = mock.aoc(None, &["verify-cache"]);
+    assert_eq!(stdout(&output), "Day 1: ok\nAll 1 cached inputs are fine\n");
+
+    mock.write("2024/inputs/1.txt", "3 4\n8 1\n5");
+    mock.write("2024/inputs/2.txt", "1 2\n3");
+    let output = mock.aoc(None, &["verify-cache"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Day 1: modified since it was downloaded\nDay 2: probably truncated\n"
+    );
+
+    // A failed download keeps the cached input
+    let output = mock.aoc(Some("expired"), &["verify-cache", "--refetch"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        mock.read("2024/inputs/1.txt").as_deref(),
+        Some("3 4\n8 1\n5")
+    );
+
+    mock.write("2024/inputs/2.txt", "1 2\n3 4\n");
+    let output = mock.aoc(Some(SESSION), &["verify-cache", "--refetch"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        mock.read("2024/inputs/1.txt").as_deref(),
+        Some("3 4\n8 1\n5 9\n")
+    );
+    let output = mock.aoc(None, &["verify-cache"]);
+    assert_eq!(
+        stdout(&output),
+        "Day 1: ok\nDay 2: no checksum recorded\nAll 2 cached inputs are fine\n"
+    );
+}
+
+#[test]
+fn test_prefetch() {
+    let mock = Mock::start("prefetch", &[]);
+    let output = mock.aoc(Some(SESSION), &["prefetch"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "Fetched: 1\nCached:  none\nLocked:  2-25\n"
+    );
+    let output = mock.aoc(Some(SESSION), &["prefetch"]);
+    assert_eq!(
+        stdout(&output),
+        "Fetched: none\nCached:  1\nLocked:  2-25\n"
+    );
+}
+
+#[test]
+fn test_leaderboard() {
+    let mock = Mock::start("leaderboard", &[]);
+    let output = mock.aoc(None, &["leaderboard", "4242"]);
+    assert!(!output.status.success());
+
+    let output = mock.aoc(Some(SESSION), &["leaderboard", "4242"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let board = stdout(&output);
+    assert!(board.starts_with("Private leaderboard 4242 of 2024, fetched 0s ago\n"));
+    assert!(board.contains("1) Mock Owner           *......"));
+    assert!(board.contains("  (anonymous user #2)  03:00:00  --:--:--\n"));
+
+    // Within 15 minutes the cached leaderboard is shown, even without a session
+    let output = mock.aoc(None, &["leaderboard", "4242"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("1) Mock Owner"));
+}
+
+#[test]
+fn test_stats() {
+    let mock = Mock::start("stats", &[]);
+    let output = mock.aoc(None, &["stats"]);
+    assert!(!output.status.success());
+
+    let output = mock.run(env!("CARGO_BIN_EXE_1"), Some(SESSION), &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(mock.read("2024/stats/runtimes.json").is_some());
+
+    let output = mock.aoc(Some(SESSION), &["stats"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stats = stdout(&output);
+    assert!(stats.contains("\n  1   00:13:20    2345      0   01:00:00    3456      0   "));
+    assert!(stats.ends_with(
+        "\n  2       >24h   45123      0          -       -      -           -          -\n"
+    ));
+
+    // The personal leaderboard is stored, so it is shown without a session as well
+    assert!(mock.read("2024/stats/personal.json").is_some());
+    let output = mock.aoc(None, &["stats"]);
+    assert_eq!(stdout(&output), stats);
+}
+
+#[test]
+fn test_day_options() {
+    let mock = Mock::start("day-options", &[]);
+    let day = |args: &[&str]| mock.run(env!("CARGO_BIN_EXE_1"), Some(SESSION), args);
+
+    let output = day(&["--part", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Part two: 0\n");
+
+    let output = day(&["--help"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Options:\n"));
+
+    mock.write("2024/examples/1/default.txt", "1 2\n2 1\n");
+    mock.write(
+        "2024/examples/1/default.answers.toml",
+        "part_one = \"0\"\npart_two = \"4\"\n",
+    );
+    let output = day(&["--example"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Part one: 0\nPart two: 3\n");
+    assert_eq!(
+        stderr(&output),
+        "Warning: the expected answer of part two for example default is 4\n"
+    );
+
+    let output = day(&["--example", "--submit", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("The answers of examples can't be submitted\n"));
+    for args in [
+        &["--input", "other.txt", "--submit", "1"][..],
+        &["--stdin", "--submit", "1"],
+    ] {
+        let output = day(args);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(
+            stderr(&output).starts_with("Only answers of your own puzzle input can be submitted\n")
+        );
+    }
+}
+
+#[test]
+fn test_redirected_input_is_not_submitted() {
+    let mock = Mock::start("redirected", &[]);
+    mock.write("2024/answers/1.txt", "1\tcorrect\t4\n");
+    mock.write("other.txt", "1 2\n");
+    let output = mock.run_redirected(
+        env!("CARGO_BIN_EXE_1"),
+        Some(SESSION),
+        &["--submit", "1"],
+        "other.txt",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "Part one: 1\n");
+    // No warning about the accepted answer of the own input either
+    assert_eq!(
+        stderr(&output),
+        "Not submitting, the input came from stdin instead of adventofcode.com\n"
+    );
+    assert_eq!(
+        mock.read("2024/answers/1.txt").as_deref(),
+        Some("1\tcorrect\t4\n")
+    );
+    assert_eq!(mock.read("2024/stats/runtimes.json"), None);
+}
+
+#[test]
+fn test_idle_pipe() {
+    let mock = Mock::start("idle-pipe", &[]);
+    let mut day = mock
+        .command(env!("CARGO_BIN_EXE_1"), Some(SESSION), &[])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Nothing is written to stdin and it stays open, so reading it would never finish
+    let _stdin = day.stdin.take();
+    let start = Instant::now();
+    while day.try_wait().unwrap().is_none() {
+        if start.elapsed() > Duration::from_secs(10) {
+            day.kill().ok();
+            panic!("the day binary waits for stdin");
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    let output = day.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Part one: 4\nPart two: 0\n");
+}
+
+#[test]
+fn test_min_interval() {
+    let mock = Mock::start("min-interval", &[]);
+    let start = Instant::now();
+    // Both processes send their request right away, but have to take turns
+    let checks: Vec<_> = (0..2)
+        .map(|_| {
+            mock.command(
+                env!("CARGO_BIN_EXE_aoc"),
+                Some(SESSION),
+                &["session", "check"],
+            )
+            .env("AOC_MIN_INTERVAL", "1")
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap()
+        })
+        .collect();
+    for mut check in checks {
+        assert!(check.wait().unwrap().success());
+    }
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn test_retry() {
+    // Downloads are retried after a server error
+    let mock = Mock::start("retry-get", &["--fail-first", "1"]);
+    let output = mock.aoc(Some(SESSION), &["fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Fetched day 1 of 2024 (3 lines)\n");
+
+    // Answers are not, the server might have checked the answer before failing
+    let mock = Mock::start("retry-post", &["--fail-first", "1"]);
+    let output = mock.aoc(Some(SESSION), &["submit", "1", "1", "30"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("503 Service Unavailable"));
+    assert_eq!(mock.read("2024/answers/1.txt"), None);
+    let output = mock.aoc(Some(SESSION), &["submit", "1", "1", "30"]);
+    assert!(stdout(&output).ends_with("That's the right answer!\n"));
+}